@@ -1,19 +1,585 @@
+use std::fmt;
+
+use num_traits::{PrimInt, Unsigned};
+
 // Fold over digits with O(1) space; a function that takes a closure.
 // Captures the two-loop pattern without iterator/Vec overhead.
-fn fold_digits(mut n: u32, init: u32, mut f: impl FnMut(u32, u32) -> u32) -> u32 {
+//
+// Generic, but monomorphized: each width keeps its own division, so u64
+// `% 10` is still a multiply and a shift.
+fn fold_digits<T: PrimInt + Unsigned, A>(
+    mut n: T,
+    base: T,
+    init: A,
+    mut f: impl FnMut(A, T) -> A,
+) -> A {
     let mut acc = init;
     loop {
-        acc = f(acc, n % 10);
-        n /= 10;
-        if n == 0 {
+        acc = f(acc, n % base);
+        n = n / base;
+        if n.is_zero() {
             break acc;
         }
     }
 }
 
-pub fn is_armstrong_number(num: u32) -> bool {
-    let digit_count = fold_digits(num, 0, |count, _| count + 1);
-    let sum_of_powers = fold_digits(num, 0, |sum, digit| sum + digit.pow(digit_count));
+/// Whether `num` is the sum of its own digits, each raised to the number of
+/// digits. Works for every unsigned primitive from u8 to u128.
+pub fn is_armstrong<T: PrimInt + Unsigned>(num: T) -> bool {
+    is_narcissistic(num, 10)
+}
+
+/// The sum of digit powers didn't fit in the type being checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("sum of digit powers overflowed")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// [`is_armstrong`], but saying so when the sum of powers overflows `T`
+/// rather than treating that as a plain `false`.
+pub fn try_is_armstrong<T: PrimInt + Unsigned>(num: T) -> Result<bool, Overflow> {
+    let ten = T::from(10).expect("every unsigned integer type holds 10");
+    let digit_count = fold_digits(num, ten, 0, |count, _| count + 1);
+    sum_of_powers(num, ten, digit_count)
+        .map(|sum| sum == num)
+        .ok_or(Overflow)
+}
+
+/// [`try_is_armstrong`] for u32, summing in u32 too.
+pub fn try_is_armstrong_number(num: u32) -> Result<bool, Overflow> {
+    try_is_armstrong(num)
+}
+
+/// [`is_armstrong`] with the digits written in `base` instead of ten.
+///
+/// Panics if `base` is less than 2.
+pub fn is_narcissistic<T: PrimInt + Unsigned>(num: T, base: u32) -> bool {
+    assert!(base >= 2, "base {base} has no digits to speak of");
+    // A base too big for T makes every T a single digit, and every single
+    // digit is its own first power.
+    let Some(base) = T::from(base) else {
+        return true;
+    };
+    let digit_count = fold_digits(num, base, 0, |count, _| count + 1);
+    // Powers and sums can run past T::MAX; anything that big can't be `num`.
+    sum_of_powers(num, base, digit_count) == Some(num)
+}
+
+/// Each digit of `num` in `base` raised to `power`, all added up, or `None`
+/// if that doesn't fit in a T.
+fn sum_of_powers<T: PrimInt + Unsigned>(num: T, base: T, power: u32) -> Option<T> {
+    fold_digits(num, base, Some(T::zero()), |sum, digit| {
+        sum?.checked_add(&num_traits::checked_pow(digit, power as usize)?)
+    })
+}
+
+/// Other numbers defined by what their digits add up to, built on the same
+/// digit folding as the Armstrong check.
+pub mod invariants {
+    use num_traits::{PrimInt, Unsigned};
+
+    fn ten<T: PrimInt + Unsigned>() -> T {
+        T::from(10).expect("every unsigned integer type holds 10")
+    }
+
+    /// Each digit of `num` raised to `power`, all added up, or `None` if
+    /// that doesn't fit in a T.
+    pub fn digit_power_sum<T: PrimInt + Unsigned>(num: T, power: u32) -> Option<T> {
+        super::sum_of_powers(num, ten(), power)
+    }
+
+    /// Whether `num` is the sum of its digits to the `power`th power. With
+    /// `power` equal to the digit count, that's an Armstrong number.
+    pub fn is_perfect_digital_invariant<T: PrimInt + Unsigned>(num: T, power: u32) -> bool {
+        digit_power_sum(num, power) == Some(num)
+    }
+
+    /// Whether repeatedly replacing `num` by the sum of the squares of its
+    /// digits reaches 1. Zero isn't happy.
+    pub fn is_happy(num: u64) -> bool {
+        // Twenty squared nines make 1620, so after one step everything is
+        // small. Every unhappy number ends up in the cycle through 4.
+        let mut n = num;
+        loop {
+            match n {
+                0 | 4 => return false,
+                1 => return true,
+                _ => n = digit_power_sum(n, 2).expect("at most 1620"),
+            }
+        }
+    }
+}
+
+/// The Armstrong numbers in `range`, smallest first, checked across rayon's
+/// thread pool.
+#[cfg(feature = "rayon")]
+pub fn armstrong_numbers_par(range: std::ops::Range<u64>) -> Vec<u64> {
+    use rayon::prelude::*;
+
+    range.into_par_iter().filter(|&n| is_armstrong(n)).collect()
+}
+
+/// [`is_armstrong`] for numbers of any size.
+#[cfg(feature = "bigint")]
+pub fn is_armstrong_big(num: &num_bigint::BigUint) -> bool {
+    is_narcissistic_big(num, 10)
+}
+
+/// [`is_narcissistic`] for numbers of any size. Panics unless `base` is
+/// between 2 and 256.
+#[cfg(feature = "bigint")]
+pub fn is_narcissistic_big(num: &num_bigint::BigUint, base: u32) -> bool {
+    use num_bigint::BigUint;
+
+    assert!(
+        (2..=256).contains(&base),
+        "can only split big numbers into bases 2 to 256, not {base}"
+    );
+    let digits = num.to_radix_le(base);
+    let digit_count = digits.len() as u32;
+    let sum_of_powers: BigUint = digits
+        .into_iter()
+        .map(|digit| BigUint::from(digit).pow(digit_count))
+        .sum();
+
+    sum_of_powers == *num
+}
+
+/// Every number narcissistic in `base` up to `u128::MAX`, smallest first,
+/// by trying them all.
+///
+/// That's all of them in base 10, whose biggest has 39 digits, but bigger
+/// bases can have some past `u128::MAX` that this never reaches. Each base
+/// only has finitely many, and the search stops past the longest number that
+/// could be one, but the last few are huge: expect the tail of this to take
+/// a very long time outside the smallest bases.
+pub fn narcissistic_numbers(base: u32) -> impl Iterator<Item = u128> {
+    assert!(base >= 2, "base {base} has no digits to speak of");
+    // `d` digits sum to at most d·(base-1)^d, which falls behind the smallest
+    // d-digit number, base^(d-1), for good once d gets big enough.
+    let fits = |d: i32| f64::from(d) * f64::from(base - 1).powi(d) >= f64::from(base).powi(d - 1);
+    let max_digits = (1..).take_while(|&d| fits(d)).last().unwrap_or(1) as u32;
+    let limit = u128::from(base)
+        .checked_pow(max_digits)
+        .map_or(u128::MAX, |limit| limit - 1);
+    (0..=limit).filter(move |&n| is_narcissistic(n, base))
+}
+
+/// Never wrong on overflow: the sum is done in u64, where it can't. The
+/// wider checks stop as soon as the sum outgrows the type, since anything
+/// that big can't be `num`; saturating wouldn't do, as a sum stuck at MAX
+/// would match MAX itself.
+///
+/// Usable in `const` contexts:
+///
+/// ```
+/// const _: () = assert!(armstrong_numbers::is_armstrong_number(9_926_315));
+/// ```
+pub const fn is_armstrong_number(num: u32) -> bool {
+    // Ten ninth powers of nine overflow u32, but nowhere near u64.
+    is_armstrong_number_u64(num as u64)
+}
+
+// Trait methods can't be called in a `const fn`, so the fixed widths spell
+// out the same loops as `fold_digits` by hand.
+macro_rules! const_armstrong {
+    ($($name:ident: $t:ty),*) => {
+        $(
+            pub const fn $name(num: $t) -> bool {
+                let mut digit_count = 0;
+                let mut n = num;
+                loop {
+                    digit_count += 1;
+                    n /= 10;
+                    if n == 0 {
+                        break;
+                    }
+                }
+
+                let mut sum_of_powers: $t = 0;
+                n = num;
+                loop {
+                    let power = match (n % 10).checked_pow(digit_count) {
+                        Some(power) => power,
+                        None => return false,
+                    };
+                    sum_of_powers = match sum_of_powers.checked_add(power) {
+                        Some(sum) => sum,
+                        None => return false,
+                    };
+                    n /= 10;
+                    if n == 0 {
+                        break;
+                    }
+                }
+
+                sum_of_powers == num
+            }
+        )*
+    };
+}
+
+const_armstrong!(is_armstrong_number_u64: u64, is_armstrong_number_u128: u128);
+
+/// The working behind an Armstrong check, for showing *why* a number is
+/// or isn't one.
+///
+/// ```
+/// let decomposition = armstrong_numbers::armstrong_decomposition(153);
+/// assert!(decomposition.is_armstrong());
+/// assert_eq!(decomposition.to_string(), "1³ + 5³ + 3³ = 1 + 125 + 27 = 153");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decomposition {
+    pub num: u64,
+    /// Most significant first.
+    pub digits: Vec<u32>,
+    /// The digit count, which every digit gets raised to.
+    pub exponent: u32,
+    /// Each digit to the `exponent`th power, in the same order as `digits`.
+    pub powers: Vec<u128>,
+    pub sum: u128,
+}
+
+impl Decomposition {
+    pub fn is_armstrong(&self) -> bool {
+        self.sum == u128::from(self.num)
+    }
+}
+
+/// `1³ + 5³ + 3³ = 1 + 125 + 27 = 153`, or `1² + 0² = 1 + 0 = 1 ≠ 10` when
+/// the sum misses.
+impl fmt::Display for Decomposition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+        let exponent: String = self
+            .exponent
+            .to_string()
+            .bytes()
+            .map(|digit| SUPERSCRIPTS[usize::from(digit - b'0')])
+            .collect();
+        for (i, digit) in self.digits.iter().enumerate() {
+            let plus = if i == 0 { "" } else { " + " };
+            write!(f, "{plus}{digit}{exponent}")?;
+        }
+        f.write_str(" =")?;
+        for (i, power) in self.powers.iter().enumerate() {
+            let plus = if i == 0 { " " } else { " + " };
+            write!(f, "{plus}{power}")?;
+        }
+        write!(f, " = {}", self.sum)?;
+        if !self.is_armstrong() {
+            write!(f, " ≠ {}", self.num)?;
+        }
+        Ok(())
+    }
+}
+
+/// Break `num` down into its digits, their powers and the sum.
+pub fn armstrong_decomposition(num: u64) -> Decomposition {
+    let mut digits = fold_digits(num, 10, Vec::new(), |mut digits, digit| {
+        digits.push(digit as u32);
+        digits
+    });
+    digits.reverse();
+    let exponent = digits.len() as u32;
+    // Twenty nines to the twentieth still fit in a u128.
+    let powers: Vec<u128> = digits
+        .iter()
+        .map(|&digit| u128::from(digit).pow(exponent))
+        .collect();
+    let sum = powers.iter().sum();
+    Decomposition {
+        num,
+        digits,
+        exponent,
+        powers,
+        sum,
+    }
+}
+
+/// Checks many numbers with the same digit count, working out the ten digit
+/// powers once instead of for every digit of every number.
+///
+/// ```
+/// let checker = armstrong_numbers::ArmstrongChecker::new(3);
+/// let found: Vec<u64> = (100..1000).filter(|&n| checker.is_armstrong(n)).collect();
+/// assert_eq!(found, [153, 370, 371, 407]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArmstrongChecker {
+    digit_count: u32,
+    powers: [u64; 10],
+}
+
+impl ArmstrongChecker {
+    /// A checker for `digit_count`-digit numbers. Panics unless that's 1 to
+    /// 20, the digit counts a u64 can have.
+    pub fn new(digit_count: u32) -> Self {
+        assert!(
+            (1..=20).contains(&digit_count),
+            "a u64 has 1 to 20 digits, not {digit_count}"
+        );
+        Self {
+            digit_count,
+            powers: std::array::from_fn(|digit| (digit as u64).pow(digit_count)),
+        }
+    }
+
+    pub fn digit_count(&self) -> u32 {
+        self.digit_count
+    }
+
+    /// Whether `num` is an Armstrong number. Anything without exactly
+    /// [`digit_count`](Self::digit_count) digits isn't, as far as this
+    /// checker is concerned.
+    pub fn is_armstrong(&self, num: u64) -> bool {
+        let (digit_count, sum_of_powers) =
+            fold_digits(num, 10, (0, Some(0u64)), |(count, sum), digit| {
+                let power = self.powers[digit as usize];
+                (count + 1, sum.and_then(|sum| sum.checked_add(power)))
+            });
+
+        digit_count == self.digit_count && sum_of_powers == Some(num)
+    }
+}
+
+/// Every base-10 Armstrong number, smallest first: zero and the 88 positive
+/// ones. None can have more than 60 digits, since 61 nines to the 61st power
+/// don't reach 61 digits, and the biggest actually has 39.
+///
+/// [`armstrong_numbers_with_digits`] finds these, but all 39 digit counts
+/// still take seconds, too long to spend on first use for a list that never
+/// changes, so they're written out here instead. The tests keep the two in step, checking every
+/// count up to 20 digits and the widest, 39.
+static ARMSTRONG_NUMBERS: [u128; 89] = [
+    0,
+    1,
+    2,
+    3,
+    4,
+    5,
+    6,
+    7,
+    8,
+    9,
+    153,
+    370,
+    371,
+    407,
+    1634,
+    8208,
+    9474,
+    54748,
+    92727,
+    93084,
+    548834,
+    1741725,
+    4210818,
+    9800817,
+    9926315,
+    24678050,
+    24678051,
+    88593477,
+    146511208,
+    472335975,
+    534494836,
+    912985153,
+    4679307774,
+    32164049650,
+    32164049651,
+    40028394225,
+    42678290603,
+    44708635679,
+    49388550606,
+    82693916578,
+    94204591914,
+    28116440335967,
+    4338281769391370,
+    4338281769391371,
+    21897142587612075,
+    35641594208964132,
+    35875699062250035,
+    1517841543307505039,
+    3289582984443187032,
+    4498128791164624869,
+    4929273885928088826,
+    63105425988599693916,
+    128468643043731391252,
+    449177399146038697307,
+    21887696841122916288858,
+    27879694893054074471405,
+    27907865009977052567814,
+    28361281321319229463398,
+    35452590104031691935943,
+    174088005938065293023722,
+    188451485447897896036875,
+    239313664430041569350093,
+    1550475334214501539088894,
+    1553242162893771850669378,
+    3706907995955475988644380,
+    3706907995955475988644381,
+    4422095118095899619457938,
+    121204998563613372405438066,
+    121270696006801314328439376,
+    128851796696487777842012787,
+    174650464499531377631639254,
+    177265453171792792366489765,
+    14607640612971980372614873089,
+    19008174136254279995012734740,
+    19008174136254279995012734741,
+    23866716435523975980390369295,
+    1145037275765491025924292050346,
+    1927890457142960697580636236639,
+    2309092682616190307509695338915,
+    17333509997782249308725103962772,
+    186709961001538790100634132976990,
+    186709961001538790100634132976991,
+    1122763285329372541592822900204593,
+    12639369517103790328947807201478392,
+    12679937780272278566303885594196922,
+    1219167219625434121569735803609966019,
+    12815792078366059955099770545296129367,
+    115132219018763992565095597973971522400,
+    115132219018763992565095597973971522401,
+];
+
+/// All the Armstrong numbers there are, smallest first.
+pub fn all_armstrong_numbers() -> impl Iterator<Item = u128> {
+    ARMSTRONG_NUMBERS.iter().copied()
+}
+
+/// The `n`th Armstrong number counting from zero, which is the 0th.
+pub fn nth_armstrong(n: usize) -> Option<u128> {
+    ARMSTRONG_NUMBERS.get(n).copied()
+}
+
+/// The Armstrong numbers with exactly `digit_count` digits, smallest first.
+///
+/// Rather than trying every number, this tries every multiset of digits:
+/// the sum of powers doesn't care what order the digits come in, so each
+/// multiset has just one sum to check, and it's an Armstrong number if its
+/// own digits are that multiset. Choosing the count of nines first, then
+/// eights and so on, lets whole branches go as soon as the sum is out of
+/// range. Panics past 39 digits, where the sums no longer fit in a u128.
+pub fn armstrong_numbers_with_digits(digit_count: u32) -> Vec<u128> {
+    assert!(
+        (1..=39).contains(&digit_count),
+        "can search 1 to 39 digits, not {digit_count}"
+    );
+    let search = Search {
+        digit_count,
+        powers: std::array::from_fn(|digit| (digit as u128).pow(digit_count)),
+        low: if digit_count == 1 {
+            0
+        } else {
+            10u128.pow(digit_count - 1)
+        },
+        high: 10u128
+            .checked_pow(digit_count)
+            .map_or(u128::MAX, |limit| limit - 1),
+    };
+    let mut found = Vec::new();
+    search.choose(9, digit_count, 0, &mut [0; 10], &mut found);
+    found.sort_unstable();
+    found
+}
+
+struct Search {
+    digit_count: u32,
+    powers: [u128; 10],
+    low: u128,
+    high: u128,
+}
 
-    sum_of_powers == num
+impl Search {
+    /// Try every count of `digit` from the `left` digits still to place,
+    /// then recurse on the next digit down.
+    fn choose(
+        &self,
+        digit: usize,
+        left: u32,
+        sum: u128,
+        counts: &mut [u32; 10],
+        found: &mut Vec<u128>,
+    ) {
+        // Even making every remaining digit this one can't reach `low`.
+        let most = sum.saturating_add(self.powers[digit].saturating_mul(left.into()));
+        if sum > self.high || most < self.low {
+            return;
+        }
+        if !self.prefix_fits(sum.max(self.low), most.min(self.high), digit, left, counts) {
+            return;
+        }
+        if digit == 0 {
+            counts[0] = left;
+            if self.digits_match(sum, counts) {
+                found.push(sum);
+            }
+            return;
+        }
+        for count in (0..=left).rev() {
+            counts[digit] = count;
+            // Too many of a big digit can overflow; no u128 sum is that big.
+            let Some(sum) = self.powers[digit]
+                .checked_mul(u128::from(count))
+                .and_then(|power| sum.checked_add(power))
+            else {
+                continue;
+            };
+            self.choose(digit - 1, left - count, sum, counts, found);
+        }
+    }
+
+    /// Every number from `lo` to `hi` starts with the digits they share, so
+    /// those have to fit the counts: no more of a digit above `digit` than
+    /// already chosen, and no more of the rest than the `left` to come.
+    fn prefix_fits(&self, lo: u128, hi: u128, digit: usize, left: u32, counts: &[u32; 10]) -> bool {
+        let (lo, hi) = (self.digits_of(lo), self.digits_of(hi));
+        let mut used = [0; 10];
+        let mut undecided = 0;
+        for i in (0..self.digit_count as usize).rev() {
+            if lo[i] != hi[i] {
+                break;
+            }
+            let d = lo[i] as usize;
+            if d > digit {
+                used[d] += 1;
+                if used[d] > counts[d] {
+                    return false;
+                }
+            } else {
+                undecided += 1;
+                if undecided > left {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// The digits of `n`, least significant first.
+    fn digits_of(&self, mut n: u128) -> [u8; 39] {
+        let mut digits = [0; 39];
+        for digit in digits.iter_mut().take(self.digit_count as usize) {
+            *digit = (n % 10) as u8;
+            n /= 10;
+        }
+        digits
+    }
+
+    fn digits_match(&self, sum: u128, counts: &[u32; 10]) -> bool {
+        let mut seen = [0; 10];
+        let digit_count = fold_digits(sum, 10, 0, |digit_count, digit| {
+            seen[digit as usize] += 1;
+            digit_count + 1
+        });
+        digit_count == self.digit_count && seen == *counts
+    }
 }
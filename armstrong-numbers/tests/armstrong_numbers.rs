@@ -44,3 +44,333 @@ fn seven_digit_number_that_is_an_armstrong_number() {
 fn seven_digit_number_that_is_not_an_armstrong_number() {
     assert!(!is_armstrong_number(9_926_314))
 }
+
+#[test]
+fn ten_digit_u32_does_not_overflow() {
+    assert!(!is_armstrong_number(4_106_098_957));
+    assert!(!is_armstrong_number(u32::MAX));
+}
+
+#[test]
+fn u64_armstrong_numbers() {
+    assert!(is_armstrong_number_u64(4_679_307_774));
+    assert!(is_armstrong_number_u64(28_116_440_335_967));
+    assert!(is_armstrong_number_u64(4_929_273_885_928_088_826));
+    assert!(!is_armstrong_number_u64(4_929_273_885_928_088_827));
+    assert!(!is_armstrong_number_u64(u64::MAX));
+    assert!(is_armstrong_number_u128(63_105_425_988_599_693_916));
+}
+
+#[test]
+fn u128_armstrong_numbers() {
+    assert!(is_armstrong_number_u128(153));
+    assert!(is_armstrong_number_u128(
+        115_132_219_018_763_992_565_095_597_973_971_522_401
+    ));
+    assert!(!is_armstrong_number_u128(
+        115_132_219_018_763_992_565_095_597_973_971_522_402
+    ));
+    assert!(!is_armstrong_number_u128(u128::MAX));
+}
+
+#[test]
+fn generic_over_unsigned_widths() {
+    assert!(is_armstrong(153u8));
+    assert!(!is_armstrong(255u8));
+    assert!(is_armstrong(9_474u16));
+    assert!(is_armstrong(54_748u16));
+    assert!(!is_armstrong(u16::MAX));
+    assert!(is_armstrong(9_926_315u32));
+    assert!(is_armstrong(4_679_307_774usize));
+    assert!(is_armstrong(28_116_440_335_967u64));
+    assert!(is_armstrong(
+        115_132_219_018_763_992_565_095_597_973_971_522_401u128
+    ));
+}
+
+#[test]
+fn generic_agrees_with_u32_check() {
+    for n in 0..100_000u32 {
+        assert_eq!(is_armstrong(n), is_armstrong_number(n), "{n}");
+    }
+}
+
+#[test]
+fn narcissistic_in_base_ten_is_armstrong() {
+    for n in 0..10_000u32 {
+        assert_eq!(is_narcissistic(n, 10), is_armstrong(n), "{n}");
+    }
+}
+
+#[test]
+fn narcissistic_in_other_bases() {
+    // In binary, only 0 and 1.
+    assert!(is_narcissistic(1u32, 2));
+    assert!(!is_narcissistic(0b10u32, 2));
+    // 17 is 122 in base 3: 1 + 8 + 8.
+    assert!(is_narcissistic(17u32, 3));
+    // 0x156 is 342: 1 + 125 + 216.
+    assert!(is_narcissistic(0x156u32, 16));
+    assert!(!is_narcissistic(0x157u32, 16));
+}
+
+#[test]
+fn base_too_big_for_the_type() {
+    assert!(is_narcissistic(200u8, 1_000));
+}
+
+#[test]
+#[should_panic]
+fn base_one_is_rejected() {
+    is_narcissistic(1u32, 1);
+}
+
+#[test]
+fn enumerates_narcissistic_numbers_per_base() {
+    let base_3: Vec<u128> = narcissistic_numbers(3).take(6).collect();
+    assert_eq!(base_3, [0, 1, 2, 5, 8, 17]);
+    let base_10: Vec<u128> = narcissistic_numbers(10).take(14).collect();
+    assert_eq!(base_10, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 153, 370, 371, 407]);
+}
+
+#[test]
+fn narcissistic_numbers_run_out() {
+    assert_eq!(narcissistic_numbers(2).collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(
+        narcissistic_numbers(3).collect::<Vec<_>>(),
+        [0, 1, 2, 5, 8, 17]
+    );
+}
+
+#[test]
+fn there_are_88_positive_armstrong_numbers() {
+    assert_eq!(all_armstrong_numbers().filter(|&n| n > 0).count(), 88);
+    assert!(all_armstrong_numbers().all(is_armstrong));
+    let all: Vec<u128> = all_armstrong_numbers().collect();
+    assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn nth_armstrong_number() {
+    assert_eq!(nth_armstrong(0), Some(0));
+    assert_eq!(nth_armstrong(10), Some(153));
+    assert_eq!(
+        nth_armstrong(88),
+        Some(115_132_219_018_763_992_565_095_597_973_971_522_401)
+    );
+    assert_eq!(nth_armstrong(89), None);
+}
+
+#[test]
+fn table_matches_brute_force_below_a_million() {
+    let brute: Vec<u128> = (0..1_000_000u128).filter(|&n| is_armstrong(n)).collect();
+    let table: Vec<u128> = all_armstrong_numbers()
+        .take_while(|&n| n < 1_000_000)
+        .collect();
+    assert_eq!(table, brute);
+}
+
+#[test]
+fn digit_search_matches_table() {
+    for digit_count in 1..=20 {
+        let table: Vec<u128> = all_armstrong_numbers()
+            .filter(|n| n.to_string().len() == digit_count as usize)
+            .collect();
+        assert_eq!(
+            armstrong_numbers_with_digits(digit_count),
+            table,
+            "{digit_count} digits"
+        );
+    }
+}
+
+#[test]
+fn digit_search_handles_the_widest_count() {
+    assert_eq!(
+        armstrong_numbers_with_digits(39),
+        [
+            115_132_219_018_763_992_565_095_597_973_971_522_400,
+            115_132_219_018_763_992_565_095_597_973_971_522_401,
+        ]
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_search_matches_table() {
+    let found = armstrong_numbers_par(0..1_000_000);
+    let table: Vec<u64> = all_armstrong_numbers()
+        .take_while(|&n| n < 1_000_000)
+        .map(|n| n as u64)
+        .collect();
+    assert_eq!(found, table);
+    assert_eq!(armstrong_numbers_par(154..370), []);
+}
+
+const SMALL_ARMSTRONG: [bool; 4] = [
+    is_armstrong_number(153),
+    is_armstrong_number(154),
+    is_armstrong_number_u64(4_679_307_774),
+    is_armstrong_number_u128(115_132_219_018_763_992_565_095_597_973_971_522_401),
+];
+
+#[test]
+fn checks_in_const_context() {
+    assert_eq!(SMALL_ARMSTRONG, [true, false, true, true]);
+}
+
+#[test]
+fn const_checks_agree_with_generic() {
+    for n in (0..200_000u64).chain(u64::MAX - 1_000..=u64::MAX) {
+        assert_eq!(is_armstrong_number_u64(n), is_armstrong(n), "{n}");
+    }
+    for n in all_armstrong_numbers() {
+        assert!(is_armstrong_number_u128(n), "{n}");
+        assert!(n < 10 || !is_armstrong_number_u128(n + 2), "{n}");
+    }
+}
+
+#[test]
+fn checker_finds_seven_digit_armstrong_numbers() {
+    let checker = ArmstrongChecker::new(7);
+    let found: Vec<u64> = (1_000_000..10_000_000)
+        .filter(|&n| checker.is_armstrong(n))
+        .collect();
+    assert_eq!(found, [1_741_725, 4_210_818, 9_800_817, 9_926_315]);
+}
+
+#[test]
+fn checker_rejects_other_digit_counts() {
+    let checker = ArmstrongChecker::new(3);
+    assert!(checker.is_armstrong(153));
+    assert!(!checker.is_armstrong(9_474));
+    assert!(!checker.is_armstrong(5));
+}
+
+#[test]
+fn checker_handles_twenty_digits() {
+    let checker = ArmstrongChecker::new(20);
+    assert!(!checker.is_armstrong(u64::MAX));
+    assert!(!checker.is_armstrong(10_000_000_000_000_000_000));
+}
+
+#[test]
+#[should_panic]
+fn checker_needs_a_digit() {
+    ArmstrongChecker::new(0);
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn big_numbers_agree_with_the_table() {
+    use num_bigint::BigUint;
+
+    for n in all_armstrong_numbers() {
+        assert!(is_armstrong_big(&BigUint::from(n)), "{n}");
+        assert!(n < 10 || !is_armstrong_big(&BigUint::from(n + 2)), "{n}");
+    }
+    assert!(is_armstrong_big(&BigUint::from(0u8)));
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn big_numbers_past_u128() {
+    use num_bigint::BigUint;
+
+    // Sixty nines: no number this long can be an Armstrong number.
+    let nines: BigUint = "9".repeat(60).parse().unwrap();
+    assert!(!is_armstrong_big(&nines));
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn big_numbers_in_other_bases() {
+    use num_bigint::BigUint;
+
+    assert!(is_narcissistic_big(&BigUint::from(17u8), 3));
+    assert!(is_narcissistic_big(&BigUint::from(0x156u32), 16));
+    for n in 0..2_000u32 {
+        assert_eq!(
+            is_narcissistic_big(&BigUint::from(n), 7),
+            is_narcissistic(n, 7),
+            "{n}"
+        );
+    }
+}
+
+#[test]
+fn digit_power_sums() {
+    use armstrong_numbers::invariants::digit_power_sum;
+
+    assert_eq!(digit_power_sum(153u32, 3), Some(153));
+    assert_eq!(digit_power_sum(12u32, 2), Some(5));
+    assert_eq!(digit_power_sum(0u32, 5), Some(0));
+    assert_eq!(digit_power_sum(99u8, 3), None);
+}
+
+#[test]
+fn perfect_digital_invariants() {
+    use armstrong_numbers::invariants::is_perfect_digital_invariant;
+
+    assert!(is_perfect_digital_invariant(153u32, 3));
+    // 4150 = 4^5 + 1^5 + 5^5 + 0^5 has four digits, so it isn't Armstrong.
+    assert!(is_perfect_digital_invariant(4_150u32, 5));
+    assert!(!is_armstrong(4_150u32));
+    assert!(!is_perfect_digital_invariant(4_152u32, 5));
+}
+
+#[test]
+fn happy_numbers() {
+    use armstrong_numbers::invariants::is_happy;
+
+    let happy: Vec<u64> = (0..50).filter(|&n| is_happy(n)).collect();
+    assert_eq!(happy, [1, 7, 10, 13, 19, 23, 28, 31, 32, 44, 49]);
+    assert!(!is_happy(u64::MAX));
+}
+
+#[test]
+fn decomposition_of_an_armstrong_number() {
+    let decomposition = armstrong_decomposition(153);
+    assert_eq!(decomposition.digits, [1, 5, 3]);
+    assert_eq!(decomposition.exponent, 3);
+    assert_eq!(decomposition.powers, [1, 125, 27]);
+    assert_eq!(decomposition.sum, 153);
+    assert!(decomposition.is_armstrong());
+}
+
+#[test]
+fn decomposition_shows_the_miss() {
+    let decomposition = armstrong_decomposition(10);
+    assert!(!decomposition.is_armstrong());
+    assert_eq!(decomposition.to_string(), "1² + 0² = 1 + 0 = 1 ≠ 10");
+}
+
+#[test]
+fn decomposition_of_large_numbers() {
+    let decomposition = armstrong_decomposition(u64::MAX);
+    assert_eq!(decomposition.exponent, 20);
+    assert!(!decomposition.is_armstrong());
+    assert!(decomposition.to_string().starts_with("1²⁰ + 8²⁰ + "));
+    assert!(armstrong_decomposition(4_929_273_885_928_088_826).is_armstrong());
+    assert_eq!(armstrong_decomposition(0).to_string(), "0¹ = 0 = 0");
+}
+
+#[test]
+fn try_reports_overflow() {
+    assert_eq!(try_is_armstrong_number(153), Ok(true));
+    assert_eq!(try_is_armstrong_number(154), Ok(false));
+    assert_eq!(try_is_armstrong_number(4_106_098_957), Err(Overflow));
+    assert_eq!(try_is_armstrong_number(u32::MAX), Err(Overflow));
+    assert_eq!(try_is_armstrong(255u8), Err(Overflow));
+    assert_eq!(try_is_armstrong(u128::MAX), Ok(false));
+}
+
+#[test]
+fn plain_checks_never_misanswer_at_the_top() {
+    assert!(!is_armstrong_number(4_106_098_957));
+    assert!(!is_armstrong_number(u32::MAX));
+    assert!(!is_armstrong(u8::MAX));
+    assert!(!is_armstrong(u16::MAX));
+    assert!(!is_armstrong_number_u64(u64::MAX));
+    assert!(!is_armstrong_number_u128(u128::MAX));
+}
@@ -0,0 +1,93 @@
+//! Allocations and time per canonical song, old `format!` verses against
+//! the piecewise assembly. Run with `cargo bench`.
+//!
+//! On a typical run:
+//!
+//! ```text
+//!  format!:  591.0 allocations/song,  35.70µs/song
+//!   pieces:  100.0 allocations/song,  26.53µs/song
+//! ```
+//!
+//! One allocation per verse is the `String` being returned; everything
+//! else now borrows.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Counts every allocation and reallocation on the way to the system
+/// allocator.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+// The verse as it used to be built: one `format!` over Cow bottle phrases.
+fn bottle_phrase(n: u64) -> Cow<'static, str> {
+    match n {
+        0 => Cow::Borrowed("no more bottles"),
+        1 => Cow::Borrowed("1 bottle"),
+        _ => Cow::Owned(format!("{n} bottles")),
+    }
+}
+
+fn format_verse(n: u64) -> String {
+    let (action, next) = match n {
+        0 => ("Go to the store and buy some more", 99),
+        1 => ("Take it down and pass it around", 0),
+        _ => ("Take one down and pass it around", n - 1),
+    };
+
+    let bottles = bottle_phrase(n);
+    let remaining = bottle_phrase(next);
+
+    match n {
+        0 => format!("No more bottles of beer on the wall, {bottles} of beer.\n{action}, {remaining} of beer on the wall.\n"),
+        _ => format!("{bottles} of beer on the wall, {bottles} of beer.\n{action}, {remaining} of beer on the wall.\n"),
+    }
+}
+
+const ROUNDS: u32 = 10_000;
+
+fn measure(name: &str, verse: fn(u64) -> String) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for n in 0..=99 {
+            black_box(verse(black_box(n)));
+        }
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "{name:>8}: {:>6.1} allocations/song, {:>8.2?}/song",
+        f64::from(allocations as u32) / f64::from(ROUNDS),
+        elapsed / ROUNDS,
+    );
+}
+
+fn main() {
+    assert!((0..=99).all(|n| format_verse(n) == beer_song::verse(n)));
+    measure("format!", format_verse);
+    measure("pieces", beer_song::verse);
+}
@@ -1,33 +1,794 @@
 use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+#[cfg(feature = "table")]
+use std::sync::OnceLock;
+use std::time::Duration;
 
-fn bottle_phrase(n: u32) -> Cow<'static, str> {
-    match n {
-        0 => Cow::Borrowed("no more bottles"),
-        1 => Cow::Borrowed("1 bottle"),
-        _ => Cow::Owned(format!("{n} bottles")),
+/// The moving parts of the song: what's on the wall, what it comes in, and
+/// what happens to it. Everything else is the same verse engine.
+///
+/// ```
+/// let soda = beer_song::SongTemplate::default()
+///     .beverage("soda")
+///     .container("can", "cans");
+/// assert!(soda.verse(99).starts_with("99 cans of soda on the wall"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SongTemplate<'a> {
+    locale: Locale,
+    beverage: &'a str,
+    singular: &'a str,
+    plural: &'a str,
+    take_one: &'a str,
+    take_last: &'a str,
+    restock: &'a str,
+    put_first: &'a str,
+    put_one: &'a str,
+    worded: bool,
+    full: u64,
+}
+
+/// The classic.
+const BEER: SongTemplate<'static> = SongTemplate {
+    locale: Locale::English,
+    beverage: "beer",
+    singular: "bottle",
+    plural: "bottles",
+    take_one: "Take one down and pass it around",
+    take_last: "Take it down and pass it around",
+    restock: "Go to the store and buy some more",
+    put_first: "Put one up on the wall",
+    put_one: "Put one more up on the wall",
+    worded: false,
+    full: 99,
+};
+
+impl Default for SongTemplate<'_> {
+    fn default() -> Self {
+        BEER
+    }
+}
+
+/// Roughly the time it takes to sing `line`, plus a breath between verses.
+fn line_delay(line: &str, ends_verse: bool) -> Duration {
+    let words = line.split_whitespace().count() as u64;
+    let pause = if ends_verse { 1_000 } else { 250 };
+    Duration::from_millis(300 * words + pause)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SongError {
+    /// The song counts down, so `end` can't be above `start`.
+    EndAboveStart { start: u64, end: u64 },
+}
+
+impl Display for SongError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SongError::EndAboveStart { start, end } => {
+                write!(
+                    f,
+                    "the song counts down, so it can't end at {end} after starting at {start}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SongError {}
+
+/// The language the song is sung in. Each one brings its own words for the
+/// default drink and actions, and its own grammar for counting bottles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+    Spanish,
+}
+
+impl Locale {
+    /// Where the bottles are.
+    fn wall(self) -> &'static str {
+        match self {
+            Locale::English => "on the wall",
+            Locale::German => "an der Wand",
+            Locale::Spanish => "en la pared",
+        }
+    }
+}
+
+impl SongTemplate<'static> {
+    /// The beer song in `locale`, with that language's own lyrics.
+    pub const fn localized(locale: Locale) -> Self {
+        match locale {
+            Locale::English => BEER,
+            Locale::German => SongTemplate {
+                locale,
+                beverage: "Bier",
+                singular: "Flasche",
+                plural: "Flaschen",
+                take_one: "Nimm eine herunter und reich sie herum",
+                take_last: "Nimm sie herunter und reich sie herum",
+                restock: "Geh in den Laden und kauf neue",
+                put_first: "Stell eine an die Wand",
+                put_one: "Stell noch eine an die Wand",
+                worded: false,
+                full: 99,
+            },
+            Locale::Spanish => SongTemplate {
+                locale,
+                beverage: "cerveza",
+                singular: "botella",
+                plural: "botellas",
+                take_one: "Toma una y pásala",
+                take_last: "Tómala y pásala",
+                restock: "Ve a la tienda y compra más",
+                put_first: "Pon una en la pared",
+                put_one: "Pon otra más en la pared",
+                worded: false,
+                full: 99,
+            },
+        }
+    }
+}
+
+impl<'a> SongTemplate<'a> {
+    pub fn beverage(mut self, beverage: &'a str) -> Self {
+        self.beverage = beverage;
+        self
+    }
+
+    /// The container word, e.g. `("can", "cans")`.
+    pub fn container(mut self, singular: &'a str, plural: &'a str) -> Self {
+        self.singular = singular;
+        self.plural = plural;
+        self
+    }
+
+    /// The action while more than one is left.
+    pub fn take_one(mut self, action: &'a str) -> Self {
+        self.take_one = action;
+        self
+    }
+
+    /// The action for the very last one.
+    pub fn take_last(mut self, action: &'a str) -> Self {
+        self.take_last = action;
+        self
+    }
+
+    /// The action once the wall is empty.
+    pub fn restock(mut self, action: &'a str) -> Self {
+        self.restock = action;
+        self
+    }
+
+    /// The action when counting up from an empty wall.
+    pub fn put_first(mut self, action: &'a str) -> Self {
+        self.put_first = action;
+        self
+    }
+
+    /// The action when counting up once the wall has some.
+    pub fn put_one(mut self, action: &'a str) -> Self {
+        self.put_one = action;
+        self
+    }
+
+    /// Spell the counts out ("Ninety-nine bottles…") as the canonical
+    /// lyrics do. Only English has the words; other locales keep digits.
+    pub fn worded(mut self, worded: bool) -> Self {
+        self.worded = worded;
+        self
+    }
+
+    /// How many bottles a full wall holds, which is what the store restocks
+    /// it to. A song started above this restocks to its own starting count.
+    pub fn full(mut self, full: u64) -> Self {
+        self.full = full;
+        self
+    }
+
+    pub fn verse(&self, n: u64) -> String {
+        let (action, next) = self.step_down(n, self.full.max(n));
+        self.render(n, action, next)
+    }
+
+    /// The verses from `start` down to `end`, produced one at a time so
+    /// callers can stream, paginate or stop early.
+    pub fn verses(self, start: u64, end: u64) -> impl Iterator<Item = String> + 'a {
+        let full = self.full.max(start);
+        (end..=start).rev().map(move |n| {
+            let (action, next) = self.step_down(n, full);
+            self.render(n, action, next)
+        })
+    }
+
+    pub fn sing(&self, start: u64, end: u64) -> String {
+        let mut out = String::new();
+        self.sing_to(start, end, &mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// The verses numbered within `range`, sung from the top down. An open
+    /// upper end means a full wall.
+    pub fn sing_range(&self, range: impl RangeBounds<u64>) -> String {
+        self.sing_range_by(range, 1)
+    }
+
+    /// Like [`SongTemplate::sing_range`], but only every `step`th verse
+    /// counting from the top, e.g. `step = 10` for a summary.
+    ///
+    /// # Panics
+    ///
+    /// If `step` is zero.
+    pub fn sing_range_by(&self, range: impl RangeBounds<u64>, step: usize) -> String {
+        let mut out = String::new();
+        let Some((low, high)) = self.bounds(&range) else {
+            return out;
+        };
+        let full = self.full.max(high);
+        for (i, n) in (low..=high).rev().step_by(step).enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            self.write_verse(&mut out, n, full)
+                .expect("writing to a String cannot fail");
+        }
+        out
+    }
+
+    /// The lowest and highest verse in `range`, or `None` if it's empty.
+    fn bounds(&self, range: &impl RangeBounds<u64>) -> Option<(u64, u64)> {
+        let low = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let high = match range.end_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_sub(1)?,
+            Bound::Unbounded => self.full.max(low),
+        };
+        (low <= high).then_some((low, high))
+    }
+
+    /// A verse of the song sung backwards: the wall goes from `n` to `n + 1`.
+    pub fn verse_up(&self, n: u64) -> String {
+        let (action, next) = self.step_up(n);
+        self.render(n, action, next)
+    }
+
+    /// The refilling-the-wall variant, counting up from `start` to `end`.
+    pub fn sing_up(&self, start: u64, end: u64) -> String {
+        let mut out = String::new();
+        for (i, n) in (start..=end).enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            self.write_verse_up(&mut out, n)
+                .expect("writing to a String cannot fail");
+        }
+        out
+    }
+
+    /// The song line by line for karaoke: `(verse_index, line_index, text,
+    /// suggested_delay)`, where the delay is how long to hold the line
+    /// before showing the next one. Longer lines, and the last line of each
+    /// verse, are held longer.
+    pub fn karaoke(
+        self,
+        start: u64,
+        end: u64,
+    ) -> impl Iterator<Item = (usize, usize, String, Duration)> + 'a {
+        self.verses(start, end)
+            .enumerate()
+            .flat_map(|(verse_index, verse)| {
+                let lines: Vec<String> = verse.lines().map(str::to_owned).collect();
+                let last = lines.len() - 1;
+                lines
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(line_index, text)| {
+                        let delay = line_delay(&text, line_index == last);
+                        (verse_index, line_index, text, delay)
+                    })
+            })
+    }
+
+    /// Like [`SongTemplate::sing`], but a range that counts up instead of
+    /// down is an error rather than an empty song.
+    pub fn try_sing(&self, start: u64, end: u64) -> Result<String, SongError> {
+        if end > start {
+            return Err(SongError::EndAboveStart { start, end });
+        }
+        Ok(self.sing(start, end))
+    }
+
+    /// Write the song straight into `w`, one verse after another, without
+    /// building any intermediate `String`s.
+    pub fn sing_to<W: fmt::Write + ?Sized>(&self, start: u64, end: u64, w: &mut W) -> fmt::Result {
+        let full = self.full.max(start);
+        for (i, n) in (end..=start).rev().enumerate() {
+            if i > 0 {
+                w.write_char('\n')?;
+            }
+            self.write_verse(w, n, full)?;
+        }
+        Ok(())
+    }
+
+    /// Like [`SongTemplate::sing_to`], but for byte sinks such as stdout or
+    /// a socket.
+    pub fn sing_to_io<W: io::Write + ?Sized>(
+        &self,
+        start: u64,
+        end: u64,
+        w: &mut W,
+    ) -> io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: w,
+            error: Ok(()),
+        };
+        match self.sing_to(start, end, &mut adapter) {
+            Ok(()) => Ok(()),
+            // fmt::Error carries nothing; the real cause was stashed on the way.
+            Err(fmt::Error) => adapter.error.and(Err(io::Error::other("formatter error"))),
+        }
+    }
+
+    /// The action sung over verse `n` counting down, and how many are left
+    /// after it, on a wall that holds `full` bottles when stocked.
+    fn step_down(&self, n: u64, full: u64) -> (&'a str, u64) {
+        match n {
+            0 => (self.restock, full),
+            1 => (self.take_last, 0),
+            _ => (self.take_one, n - 1),
+        }
+    }
+
+    fn step_up(&self, n: u64) -> (&'a str, u64) {
+        let action = if n == 0 { self.put_first } else { self.put_one };
+        (action, n + 1)
+    }
+
+    /// Verse `n` of a song whose wall holds `full` bottles when stocked.
+    fn write_verse<W: fmt::Write + ?Sized>(&self, w: &mut W, n: u64, full: u64) -> fmt::Result {
+        let (action, next) = self.step_down(n, full);
+        self.write_lines(w, n, action, next)
+    }
+
+    fn write_verse_up<W: fmt::Write + ?Sized>(&self, w: &mut W, n: u64) -> fmt::Result {
+        let (action, next) = self.step_up(n);
+        self.write_lines(w, n, action, next)
+    }
+
+    /// "no more bottles of beer", "1 bottle of beer", "n bottles of beer",
+    /// in the template's language. Every piece borrows from the template or
+    /// a static string except a number that has to be formatted.
+    fn count_phrase(&self, n: u64) -> [Cow<'a, str>; 5] {
+        let SongTemplate {
+            locale,
+            beverage,
+            singular,
+            plural,
+            worded,
+            ..
+        } = *self;
+        let b = Cow::Borrowed;
+
+        if n == 0 {
+            return match locale {
+                Locale::English => [b("no more "), b(plural), b(" of "), b(beverage), b("")],
+                Locale::German => [b("keine "), b(plural), b(" "), b(beverage), b(" mehr")],
+                Locale::Spanish => [b("no quedan "), b(plural), b(" de "), b(beverage), b("")],
+            };
+        }
+        let container = if n == 1 { singular } else { plural };
+        let link = match locale {
+            Locale::English => " of ",
+            Locale::German => " ",
+            Locale::Spanish => " de ",
+        };
+        let number = if worded && locale == Locale::English {
+            Cow::Owned(Words(n).to_string())
+        } else {
+            number(n)
+        };
+        [number, b(" "), b(container), b(link), b(beverage)]
+    }
+
+    /// Every verse has the same shape; only the action and where the count
+    /// ends up differ.
+    fn write_lines<W: fmt::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        n: u64,
+        action: &str,
+        next: u64,
+    ) -> fmt::Result {
+        let count = self.count_phrase(n);
+        let remaining = self.count_phrase(next);
+        let pieces = verse_pieces(&count, &remaining, self.locale.wall(), action);
+        capitalized(pieces, |piece| w.write_str(piece))
     }
+
+    /// A verse as a `String` allocated once at its final size.
+    fn render(&self, n: u64, action: &str, next: u64) -> String {
+        let count = self.count_phrase(n);
+        let remaining = self.count_phrase(next);
+        let pieces = verse_pieces(&count, &remaining, self.locale.wall(), action);
+
+        let mut out = String::with_capacity(pieces.clone().map(str::len).sum());
+        capitalized(pieces, |piece| {
+            out.push_str(piece);
+            Ok(())
+        })
+        .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+/// The two lines of a verse as a run of borrowed pieces.
+fn verse_pieces<'p>(
+    count: &'p [Cow<str>],
+    remaining: &'p [Cow<str>],
+    wall: &'p str,
+    action: &'p str,
+) -> impl Iterator<Item = &'p str> + Clone {
+    let count = count.iter().map(|piece| piece.as_ref());
+    let remaining = remaining.iter().map(|piece| piece.as_ref());
+    count
+        .clone()
+        .chain([" ", wall, ", "])
+        .chain(count)
+        .chain([".\n", action, ", "])
+        .chain(remaining)
+        .chain([" ", wall, ".\n"])
 }
 
-pub fn verse(n: u32) -> String {
-    let (action, next) = match n {
-        0 => ("Go to the store and buy some more", 99),
-        1 => ("Take it down and pass it around", 0),
-        _ => ("Take one down and pass it around", n - 1),
-    };
+/// Hand each piece to `f`, upper-casing the very first character since
+/// it starts the verse.
+fn capitalized<'p>(
+    pieces: impl Iterator<Item = &'p str>,
+    mut f: impl FnMut(&str) -> fmt::Result,
+) -> fmt::Result {
+    let mut started = false;
+    for piece in pieces {
+        let mut chars = piece.chars();
+        match chars.next() {
+            Some(first) if !started => {
+                started = true;
+                let mut buf = [0u8; 4];
+                for upper in first.to_uppercase() {
+                    f(upper.encode_utf8(&mut buf))?;
+                }
+                f(chars.as_str())?;
+            }
+            _ => f(piece)?,
+        }
+    }
+    Ok(())
+}
 
-    let bottles = bottle_phrase(n);
-    let remaining = bottle_phrase(next);
+/// Every two-digit number, back to back, so the counts in the canonical
+/// song can be borrowed instead of formatted.
+const SMALL_NUMBERS: &str = "00010203040506070809101112131415161718192021222324252627282930313233343536373839404142434445464748495051525354555657585960616263646566676869707172737475767778798081828384858687888990919293949596979899";
 
+fn number(n: u64) -> Cow<'static, str> {
     match n {
-        0 => format!( "No more bottles of beer on the wall, {bottles} of beer.\n{action}, {remaining} of beer on the wall.\n"),
-        _ => format!( "{bottles} of beer on the wall, {bottles} of beer.\n{action}, {remaining} of beer on the wall.\n"),
+        0..=9 => {
+            let i = 2 * n as usize + 1;
+            Cow::Borrowed(&SMALL_NUMBERS[i..i + 1])
+        }
+        10..=99 => {
+            let i = 2 * n as usize;
+            Cow::Borrowed(&SMALL_NUMBERS[i..i + 2])
+        }
+        _ => Cow::Owned(n.to_string()),
+    }
+}
+
+/// A song made of numbered verses.
+///
+/// Implementors only need to write a single verse; the rest of the song is
+/// assembled the same way for all of them.
+pub trait Song {
+    /// Write verse `n` into `w`, including its trailing newline.
+    fn write_verse(&self, w: &mut dyn fmt::Write, n: u64) -> fmt::Result;
+
+    /// Whether the verses are sung from the highest number down, like the
+    /// beer song, rather than up.
+    fn counts_down(&self) -> bool {
+        false
+    }
+
+    fn verse(&self, n: u64) -> String {
+        let mut out = String::new();
+        self.write_verse(&mut out, n)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// The verses numbered within `range`, in the order the song sings
+    /// them, separated by blank lines.
+    fn sing(&self, range: RangeInclusive<u64>) -> String {
+        let mut out = String::new();
+        let mut write = |i: usize, n: u64| {
+            if i > 0 {
+                out.push('\n');
+            }
+            self.write_verse(&mut out, n)
+                .expect("writing to a String cannot fail");
+        };
+        if self.counts_down() {
+            range.rev().enumerate().for_each(|(i, n)| write(i, n));
+        } else {
+            range.enumerate().for_each(|(i, n)| write(i, n));
+        }
+        out
+    }
+}
+
+impl Song for SongTemplate<'_> {
+    fn write_verse(&self, w: &mut dyn fmt::Write, n: u64) -> fmt::Result {
+        SongTemplate::write_verse(self, w, n, self.full.max(n))
+    }
+
+    fn counts_down(&self) -> bool {
+        true
+    }
+
+    fn sing(&self, range: RangeInclusive<u64>) -> String {
+        SongTemplate::sing(self, *range.end(), *range.start())
+    }
+}
+
+/// "The Twelve Days of Christmas", where every verse repeats all the gifts
+/// that came before it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TwelveDays;
+
+const ORDINALS: [&str; 12] = [
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth",
+    "eleventh", "twelfth",
+];
+const GIFTS: [&str; 12] = [
+    "a Partridge in a Pear Tree",
+    "two Turtle Doves",
+    "three French Hens",
+    "four Calling Birds",
+    "five Gold Rings",
+    "six Geese-a-Laying",
+    "seven Swans-a-Swimming",
+    "eight Maids-a-Milking",
+    "nine Ladies Dancing",
+    "ten Lords-a-Leaping",
+    "eleven Pipers Piping",
+    "twelve Drummers Drumming",
+];
+
+impl Song for TwelveDays {
+    /// # Panics
+    ///
+    /// If `n` isn't a day from 1 to 12.
+    fn write_verse(&self, w: &mut dyn fmt::Write, n: u64) -> fmt::Result {
+        assert!((1..=12).contains(&n), "there is no day {} of Christmas", n);
+        let day = n as usize;
+        write!(
+            w,
+            "On the {} day of Christmas my true love gave to me: ",
+            ORDINALS[day - 1]
+        )?;
+        for (i, gift) in GIFTS[..day].iter().enumerate().rev() {
+            match i {
+                0 if day > 1 => write!(w, "and {gift}")?,
+                0 => w.write_str(gift)?,
+                _ => write!(w, "{gift}, ")?,
+            }
+        }
+        w.write_str(".\n")
     }
 }
 
-pub fn sing(start: u32, end: u32) -> String {
-    (end..=start)
-        .rev()
-        .map(verse)
-        .collect::<Vec<_>>()
-        .join("\n")
+/// A number spelled out in English words: "one thousand two hundred
+/// thirty-four".
+struct Words(u64);
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: [(u64, &str); 6] = [
+    (1_000_000_000_000_000_000, "quintillion"),
+    (1_000_000_000_000_000, "quadrillion"),
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+impl Words {
+    /// 1..=999, the building block every scale repeats.
+    fn fmt_hundreds(f: &mut Formatter, n: u64) -> fmt::Result {
+        let (hundreds, rest) = (n / 100, n % 100);
+        if hundreds > 0 {
+            write!(f, "{} hundred", ONES[hundreds as usize])?;
+            if rest > 0 {
+                f.write_str(" ")?;
+            }
+        }
+        match rest {
+            0 => Ok(()),
+            1..=19 => f.write_str(ONES[rest as usize]),
+            _ if rest % 10 == 0 => f.write_str(TENS[(rest / 10) as usize]),
+            _ => write!(
+                f,
+                "{}-{}",
+                TENS[(rest / 10) as usize],
+                ONES[(rest % 10) as usize]
+            ),
+        }
+    }
+}
+
+impl Display for Words {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.0 == 0 {
+            return f.write_str(ONES[0]);
+        }
+        let mut rest = self.0;
+        let mut first = true;
+        for (scale, name) in SCALES.iter().copied().chain([(1, "")]) {
+            let chunk = rest / scale;
+            rest %= scale;
+            if chunk == 0 {
+                continue;
+            }
+            if !first {
+                f.write_str(" ")?;
+            }
+            first = false;
+            Self::fmt_hundreds(f, chunk)?;
+            if !name.is_empty() {
+                write!(f, " {name}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A single verse of the beer song, formatted on demand straight into
+/// whatever formatter it's displayed with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Verse(pub u64);
+
+impl Display for Verse {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        BEER.write_verse(f, self.0, BEER.full.max(self.0))
+    }
+}
+
+pub fn verse(n: u64) -> String {
+    // Same text as `Verse(n).to_string()`, but sized up front.
+    BEER.verse(n)
+}
+
+/// The canonical verses 99 down to 0, rendered once on first use and shared
+/// for the life of the program. Only compiled with the "table" feature.
+#[cfg(feature = "table")]
+static VERSE_TABLE: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Verse `n` of the canonical song without re-formatting it every call.
+/// `None` above 99, where there is no canonical verse to cache.
+#[cfg(feature = "table")]
+pub fn static_verse(n: u64) -> Option<&'static str> {
+    if n > BEER.full {
+        return None;
+    }
+    let table = VERSE_TABLE.get_or_init(|| (0..=BEER.full).map(verse).collect());
+    Some(&table[n as usize])
+}
+
+/// The verses from `start` down to `end`, produced one at a time so callers
+/// can stream, paginate or stop early.
+pub fn verses(start: u64, end: u64) -> impl Iterator<Item = String> {
+    BEER.verses(start, end)
+}
+
+pub fn sing(start: u64, end: u64) -> String {
+    BEER.sing(start, end)
+}
+
+/// The verses numbered within `range`, sung from the top down.
+pub fn sing_range(range: impl RangeBounds<u64>) -> String {
+    BEER.sing_range(range)
+}
+
+/// Every `step`th verse within `range`, counting from the top.
+pub fn sing_range_by(range: impl RangeBounds<u64>, step: usize) -> String {
+    BEER.sing_range_by(range, step)
+}
+
+pub fn verse_up(n: u64) -> String {
+    BEER.verse_up(n)
+}
+
+/// The refilling-the-wall variant, counting up from `start` to `end`.
+pub fn sing_up(start: u64, end: u64) -> String {
+    BEER.sing_up(start, end)
+}
+
+/// The beer song line by line, see [`SongTemplate::karaoke`].
+pub fn karaoke(start: u64, end: u64) -> impl Iterator<Item = (usize, usize, String, Duration)> {
+    BEER.karaoke(start, end)
+}
+
+pub fn try_sing(start: u64, end: u64) -> Result<String, SongError> {
+    BEER.try_sing(start, end)
+}
+
+/// The verse with its counts spelled out: "Ninety-nine bottles of beer…".
+pub fn verse_worded(n: u64) -> String {
+    BEER.worded(true).verse(n)
+}
+
+pub fn verse_in(locale: Locale, n: u64) -> String {
+    SongTemplate::localized(locale).verse(n)
+}
+
+pub fn sing_in(locale: Locale, start: u64, end: u64) -> String {
+    SongTemplate::localized(locale).sing(start, end)
+}
+
+/// Write the song straight into `w`, one verse after another, without
+/// building any intermediate `String`s.
+pub fn sing_to<W: fmt::Write + ?Sized>(start: u64, end: u64, w: &mut W) -> fmt::Result {
+    BEER.sing_to(start, end, w)
+}
+
+/// Like [`sing_to`], but for byte sinks such as stdout or a socket.
+pub fn sing_to_io<W: io::Write + ?Sized>(start: u64, end: u64, w: &mut W) -> io::Result<()> {
+    BEER.sing_to_io(start, end, w)
+}
+
+/// Lets the `fmt::Write` machinery drive an `io::Write`, keeping hold of
+/// the first I/O error since `fmt::Error` can't carry it.
+struct IoAdapter<'a, W: ?Sized> {
+    inner: &'a mut W,
+    error: io::Result<()>,
+}
+
+impl<W: io::Write + ?Sized> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Err(e);
+            fmt::Error
+        })
+    }
 }
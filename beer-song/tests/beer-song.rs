@@ -29,3 +29,348 @@ fn test_song_8_6() {
 fn test_song_3_0() {
     assert_eq!(beer::sing(3, 0), "3 bottles of beer on the wall, 3 bottles of beer.\nTake one down and pass it around, 2 bottles of beer on the wall.\n\n2 bottles of beer on the wall, 2 bottles of beer.\nTake one down and pass it around, 1 bottle of beer on the wall.\n\n1 bottle of beer on the wall, 1 bottle of beer.\nTake it down and pass it around, no more bottles of beer on the wall.\n\nNo more bottles of beer on the wall, no more bottles of beer.\nGo to the store and buy some more, 99 bottles of beer on the wall.\n");
 }
+
+#[test]
+fn test_verses_8_6() {
+    let verses: Vec<String> = beer::verses(8, 6).collect();
+    assert_eq!(verses, vec![beer::verse(8), beer::verse(7), beer::verse(6)]);
+}
+
+#[test]
+fn test_verses_stop_early() {
+    let mut verses = beer::verses(99, 0);
+    assert_eq!(verses.next(), Some(beer::verse(99)));
+    assert_eq!(verses.nth(98), Some(beer::verse(0)));
+    assert_eq!(verses.next(), None);
+}
+
+#[test]
+fn test_verses_empty_when_end_above_start() {
+    assert_eq!(beer::verses(3, 5).count(), 0);
+}
+
+#[test]
+fn test_sing_to_string() {
+    let mut out = String::new();
+    beer::sing_to(3, 0, &mut out).unwrap();
+    assert_eq!(out, beer::sing(3, 0));
+}
+
+#[test]
+fn test_sing_to_io() {
+    let mut out = Vec::new();
+    beer::sing_to_io(8, 6, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), beer::sing(8, 6));
+}
+
+#[test]
+fn test_sing_to_io_reports_write_errors() {
+    let mut full = [0u8; 16];
+    let mut sink = &mut full[..];
+    let err = beer::sing_to_io(99, 0, &mut sink).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_default_template_is_beer() {
+    assert_eq!(beer::SongTemplate::default().sing(3, 0), beer::sing(3, 0));
+}
+
+#[test]
+fn test_soda_template() {
+    let soda = beer::SongTemplate::default()
+        .beverage("soda")
+        .container("can", "cans")
+        .take_one("Crack one open and hand it around")
+        .take_last("Crack it open and hand it around")
+        .restock("Run to the shop and grab some more");
+    assert_eq!(soda.sing(2, 0), "2 cans of soda on the wall, 2 cans of soda.\nCrack one open and hand it around, 1 can of soda on the wall.\n\n1 can of soda on the wall, 1 can of soda.\nCrack it open and hand it around, no more cans of soda on the wall.\n\nNo more cans of soda on the wall, no more cans of soda.\nRun to the shop and grab some more, 99 cans of soda on the wall.\n");
+}
+
+#[test]
+fn test_template_verses_iterator() {
+    let tea = beer::SongTemplate::default()
+        .beverage("tea")
+        .container("cup", "cups");
+    let verses: Vec<String> = tea.verses(5, 4).collect();
+    assert_eq!(verses, vec![tea.verse(5), tea.verse(4)]);
+    assert!(verses[0].starts_with("5 cups of tea on the wall, 5 cups of tea."));
+}
+
+#[test]
+fn test_english_is_the_default_locale() {
+    assert_eq!(beer::Locale::default(), beer::Locale::English);
+    assert_eq!(beer::sing_in(beer::Locale::English, 3, 0), beer::sing(3, 0));
+}
+
+#[test]
+fn test_german_verses() {
+    use beer::Locale::German;
+    assert_eq!(beer::verse_in(German, 2), "2 Flaschen Bier an der Wand, 2 Flaschen Bier.\nNimm eine herunter und reich sie herum, 1 Flasche Bier an der Wand.\n");
+    assert_eq!(beer::verse_in(German, 1), "1 Flasche Bier an der Wand, 1 Flasche Bier.\nNimm sie herunter und reich sie herum, keine Flaschen Bier mehr an der Wand.\n");
+    assert_eq!(beer::verse_in(German, 0), "Keine Flaschen Bier mehr an der Wand, keine Flaschen Bier mehr.\nGeh in den Laden und kauf neue, 99 Flaschen Bier an der Wand.\n");
+}
+
+#[test]
+fn test_spanish_verses() {
+    use beer::Locale::Spanish;
+    assert_eq!(beer::verse_in(Spanish, 2), "2 botellas de cerveza en la pared, 2 botellas de cerveza.\nToma una y pásala, 1 botella de cerveza en la pared.\n");
+    assert_eq!(beer::verse_in(Spanish, 0), "No quedan botellas de cerveza en la pared, no quedan botellas de cerveza.\nVe a la tienda y compra más, 99 botellas de cerveza en la pared.\n");
+}
+
+#[test]
+fn test_localized_template_is_customizable() {
+    let wein = beer::SongTemplate::localized(beer::Locale::German).beverage("Wein");
+    assert!(wein
+        .verse(0)
+        .starts_with("Keine Flaschen Wein mehr an der Wand"));
+}
+
+#[test]
+fn test_verse_worded_99() {
+    assert_eq!(beer::verse_worded(99), "Ninety-nine bottles of beer on the wall, ninety-nine bottles of beer.\nTake one down and pass it around, ninety-eight bottles of beer on the wall.\n");
+}
+
+#[test]
+fn test_verse_worded_edges() {
+    assert_eq!(beer::verse_worded(1), "One bottle of beer on the wall, one bottle of beer.\nTake it down and pass it around, no more bottles of beer on the wall.\n");
+    assert_eq!(beer::verse_worded(0), "No more bottles of beer on the wall, no more bottles of beer.\nGo to the store and buy some more, ninety-nine bottles of beer on the wall.\n");
+    assert!(beer::verse_worded(21).starts_with("Twenty-one bottles"));
+    assert!(beer::verse_worded(40).starts_with("Forty bottles"));
+    assert!(beer::verse_worded(13).starts_with("Thirteen bottles"));
+}
+
+#[test]
+fn test_worded_large_numbers() {
+    assert!(beer::verse_worded(1_234).starts_with("One thousand two hundred thirty-four bottles"));
+    assert!(beer::verse_worded(2_000_000_005).starts_with("Two billion five bottles"));
+    assert!(beer::verse_worded(100).ends_with("ninety-nine bottles of beer on the wall.\n"));
+}
+
+#[test]
+fn test_worded_template_option() {
+    let worded = beer::SongTemplate::default().worded(true);
+    assert_eq!(worded.verse(5), beer::verse_worded(5));
+    // Other locales keep digits.
+    let german = beer::SongTemplate::localized(beer::Locale::German).worded(true);
+    assert_eq!(german.verse(2), beer::verse_in(beer::Locale::German, 2));
+}
+
+#[test]
+fn test_sing_from_ten_thousand() {
+    let song = beer::sing(10_000, 0);
+    assert!(song.starts_with("10000 bottles of beer on the wall, 10000 bottles of beer.\nTake one down and pass it around, 9999 bottles of beer on the wall.\n\n"));
+    assert!(
+        song.ends_with("Go to the store and buy some more, 10000 bottles of beer on the wall.\n")
+    );
+    assert_eq!(song.matches("on the wall,").count(), 10_001);
+}
+
+#[test]
+fn test_restock_to_starting_count() {
+    assert!(beer::verses(150, 0)
+        .last()
+        .unwrap()
+        .ends_with("buy some more, 150 bottles of beer on the wall.\n"));
+    // Excerpts of the canonical song still restock to a full wall of 99.
+    assert!(beer::sing(2, 0).ends_with("buy some more, 99 bottles of beer on the wall.\n"));
+}
+
+#[test]
+fn test_template_full_wall() {
+    let small = beer::SongTemplate::default().full(10);
+    assert!(small
+        .verse(0)
+        .ends_with("buy some more, 10 bottles of beer on the wall.\n"));
+}
+
+#[test]
+fn test_u64_counts() {
+    let n = u64::from(u32::MAX) + 2;
+    assert_eq!(beer::verse(n), "4294967297 bottles of beer on the wall, 4294967297 bottles of beer.\nTake one down and pass it around, 4294967296 bottles of beer on the wall.\n");
+}
+
+#[test]
+fn test_try_sing_valid_range() {
+    assert_eq!(beer::try_sing(8, 6), Ok(beer::sing(8, 6)));
+    assert_eq!(beer::try_sing(0, 0), Ok(beer::verse(0)));
+}
+
+#[test]
+fn test_try_sing_rejects_inverted_range() {
+    assert_eq!(
+        beer::try_sing(6, 8),
+        Err(beer::SongError::EndAboveStart { start: 6, end: 8 })
+    );
+}
+
+#[test]
+fn test_song_error_explains_itself() {
+    let error: Box<dyn std::error::Error> = beer::try_sing(6, 8).unwrap_err().into();
+    assert_eq!(
+        error.to_string(),
+        "the song counts down, so it can't end at 8 after starting at 6"
+    );
+}
+
+#[test]
+fn test_verse_display() {
+    assert_eq!(beer::Verse(8).to_string(), beer::verse(8));
+    assert_eq!(format!("{}", beer::Verse(0)), beer::verse(0));
+}
+
+#[test]
+fn test_verse_formats_into_existing_buffer() {
+    use std::fmt::Write;
+    let mut out = String::from("> ");
+    write!(out, "{}", beer::Verse(1)).unwrap();
+    assert_eq!(out, format!("> {}", beer::verse(1)));
+}
+
+#[cfg(feature = "table")]
+#[test]
+fn test_static_verse_matches_verse() {
+    for n in 0..=99 {
+        assert_eq!(beer::static_verse(n), Some(beer::verse(n).as_str()));
+    }
+}
+
+#[cfg(feature = "table")]
+#[test]
+fn test_static_verse_is_shared() {
+    let first = beer::static_verse(42).unwrap();
+    let second = beer::static_verse(42).unwrap();
+    assert!(std::ptr::eq(first, second));
+    assert_eq!(beer::static_verse(100), None);
+}
+
+#[test]
+fn test_beer_song_trait_matches_free_functions() {
+    use beer::Song;
+    let song = beer::SongTemplate::default();
+    assert_eq!(Song::verse(&song, 5), beer::verse(5));
+    assert_eq!(Song::sing(&song, 0..=3), beer::sing(3, 0));
+}
+
+#[test]
+fn test_twelve_days_verses() {
+    use beer::Song;
+    assert_eq!(
+        beer::TwelveDays.verse(1),
+        "On the first day of Christmas my true love gave to me: a Partridge in a Pear Tree.\n"
+    );
+    assert_eq!(beer::TwelveDays.verse(3), "On the third day of Christmas my true love gave to me: three French Hens, two Turtle Doves, and a Partridge in a Pear Tree.\n");
+    assert!(beer::TwelveDays.verse(12).starts_with("On the twelfth day of Christmas my true love gave to me: twelve Drummers Drumming, eleven Pipers Piping,"));
+}
+
+#[test]
+fn test_twelve_days_sings_upwards() {
+    use beer::Song;
+    let song = beer::TwelveDays.sing(1..=2);
+    assert_eq!(
+        song,
+        format!(
+            "{}\n{}",
+            beer::TwelveDays.verse(1),
+            beer::TwelveDays.verse(2)
+        )
+    );
+}
+
+#[test]
+fn test_songs_as_trait_objects() {
+    let songs: [&dyn beer::Song; 2] = [&beer::TwelveDays, &beer::SongTemplate::default()];
+    assert!(songs[0].verse(2).contains("two Turtle Doves"));
+    assert!(songs[1].verse(2).starts_with("2 bottles"));
+}
+
+#[test]
+#[should_panic]
+fn test_twelve_days_has_no_thirteenth_day() {
+    use beer::Song;
+    beer::TwelveDays.verse(13);
+}
+
+#[test]
+fn test_karaoke_lines() {
+    let lines: Vec<_> = beer::karaoke(2, 1).collect();
+    let indices: Vec<_> = lines.iter().map(|(v, l, _, _)| (*v, *l)).collect();
+    assert_eq!(indices, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+    assert_eq!(
+        lines[0].2,
+        "2 bottles of beer on the wall, 2 bottles of beer."
+    );
+    assert_eq!(
+        lines[3].2,
+        "Take it down and pass it around, no more bottles of beer on the wall."
+    );
+}
+
+#[test]
+fn test_karaoke_rejoins_into_the_song() {
+    let mut verses: Vec<String> = Vec::new();
+    for (verse, _, text, _) in beer::karaoke(3, 0) {
+        if verses.len() == verse {
+            verses.push(String::new());
+        }
+        verses[verse].push_str(&text);
+        verses[verse].push('\n');
+    }
+    assert_eq!(verses.join("\n"), beer::sing(3, 0));
+}
+
+#[test]
+fn test_karaoke_pauses_longer_between_verses() {
+    let lines: Vec<_> = beer::karaoke(5, 5).collect();
+    assert!(lines[1].3 > lines[0].3);
+    assert!(lines.iter().all(|(_, _, _, delay)| !delay.is_zero()));
+}
+
+#[test]
+fn test_verse_up() {
+    assert_eq!(beer::verse_up(0), "No more bottles of beer on the wall, no more bottles of beer.\nPut one up on the wall, 1 bottle of beer on the wall.\n");
+    assert_eq!(beer::verse_up(1), "1 bottle of beer on the wall, 1 bottle of beer.\nPut one more up on the wall, 2 bottles of beer on the wall.\n");
+}
+
+#[test]
+fn test_sing_up_0_2() {
+    assert_eq!(beer::sing_up(0, 2), "No more bottles of beer on the wall, no more bottles of beer.\nPut one up on the wall, 1 bottle of beer on the wall.\n\n1 bottle of beer on the wall, 1 bottle of beer.\nPut one more up on the wall, 2 bottles of beer on the wall.\n\n2 bottles of beer on the wall, 2 bottles of beer.\nPut one more up on the wall, 3 bottles of beer on the wall.\n");
+}
+
+#[test]
+fn test_sing_up_localized() {
+    let german = beer::SongTemplate::localized(beer::Locale::German);
+    assert_eq!(german.verse_up(0), "Keine Flaschen Bier mehr an der Wand, keine Flaschen Bier mehr.\nStell eine an die Wand, 1 Flasche Bier an der Wand.\n");
+}
+
+#[test]
+fn test_sing_range_matches_sing() {
+    assert_eq!(beer::sing_range(6..=8), beer::sing(8, 6));
+    assert_eq!(beer::sing_range(6..9), beer::sing(8, 6));
+    assert_eq!(beer::sing_range(..=3), beer::sing(3, 0));
+    assert_eq!(beer::sing_range(..), beer::sing(99, 0));
+    assert_eq!(beer::sing_range(97..), beer::sing(99, 97));
+}
+
+#[test]
+fn test_sing_range_empty() {
+    assert_eq!(beer::sing_range(5..5), "");
+    assert_eq!(beer::sing_range(..0), "");
+}
+
+#[test]
+fn test_sing_range_by_step() {
+    let summary = beer::sing_range_by(..=99, 10);
+    let expected: Vec<String> = [99, 89, 79, 69, 59, 49, 39, 29, 19, 9]
+        .iter()
+        .map(|&n| beer::verse(n))
+        .collect();
+    assert_eq!(summary, expected.join("\n"));
+}
+
+#[test]
+fn test_sing_range_above_99_restocks_to_top() {
+    assert!(
+        beer::sing_range(0..=120).ends_with("buy some more, 120 bottles of beer on the wall.\n")
+    );
+}
@@ -1,4 +1,16 @@
+use std::cmp::Ordering;
+
 pub fn find<T: Ord>(array: impl AsRef<[T]>, key: T) -> Option<usize> {
+    find_by(array, |probe| probe.cmp(&key))
+}
+
+/// Search with `f` saying whether each probed element is less than, equal
+/// to or greater than the one being looked for, like
+/// [`slice::binary_search_by`].
+pub fn find_by<T, F>(array: impl AsRef<[T]>, mut f: F) -> Option<usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
     let ary = array.as_ref();
 
     let mut l = 0;
@@ -6,15 +18,21 @@ pub fn find<T: Ord>(array: impl AsRef<[T]>, key: T) -> Option<usize> {
 
     while l < r {
         let m = (l + r) / 2;
-        if ary[m] == key {
-            return Some(m);
-        }
-
-        if key < ary[m] {
-            r = m;
-        } else {
-            l = m + 1;
+        match f(&ary[m]) {
+            Ordering::Equal => return Some(m),
+            Ordering::Greater => r = m,
+            Ordering::Less => l = m + 1,
         }
     }
     None
 }
+
+/// Search for the element whose key, as `f` pulls it out, is `key`. The
+/// array has to be sorted by that key.
+pub fn find_by_key<T, K, F>(array: impl AsRef<[T]>, key: K, mut f: F) -> Option<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    find_by(array, |probe| f(probe).cmp(&key))
+}
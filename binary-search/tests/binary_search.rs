@@ -81,3 +81,39 @@ fn works_for_str_elements() {
     assert_eq!(find(["a"], "a"), Some(0));
     assert_eq!(find(["a", "b"], "b"), Some(1));
 }
+
+#[derive(Debug)]
+struct Employee {
+    id: u32,
+    name: &'static str,
+}
+
+const STAFF: [Employee; 4] = [
+    Employee { id: 3, name: "Ada" },
+    Employee { id: 7, name: "Grace" },
+    Employee { id: 12, name: "Edsger" },
+    Employee { id: 40, name: "Barbara" },
+];
+
+#[test]
+fn find_by_with_a_comparator() {
+    assert_eq!(find_by(&STAFF, |e| e.id.cmp(&12)), Some(2));
+    assert_eq!(find_by(&STAFF, |e| e.id.cmp(&8)), None);
+    assert_eq!(find_by(&[1, 3, 5], |probe: &i32| probe.cmp(&1)), Some(0));
+    assert_eq!(find_by(&[] as &[i32], |probe: &i32| probe.cmp(&1)), None);
+}
+
+#[test]
+fn find_by_key_with_a_field() {
+    assert_eq!(find_by_key(&STAFF, 40, |e| e.id), Some(3));
+    assert_eq!(find_by_key(&STAFF, 3, |e| e.id).map(|i| STAFF[i].name), Some("Ada"));
+    assert_eq!(find_by_key(&STAFF, 41, |e| e.id), None);
+    assert_eq!(find_by_key(&STAFF, 0, |e| e.id), None);
+}
+
+#[test]
+fn find_by_reverse_order() {
+    let descending = [9, 7, 4, 1];
+    assert_eq!(find_by(&descending, |probe| 4.cmp(probe)), Some(2));
+    assert_eq!(find_by_key(&descending, std::cmp::Reverse(9), |&n| std::cmp::Reverse(n)), Some(0));
+}
@@ -1,5 +1,8 @@
-#[derive(Debug)]
-struct Sentiment {
+use std::io::{self, Read};
+
+/// The features of a message that every responder gets to work with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sentiment {
     is_question: bool,
     is_yelling: bool,
     is_silent: bool,
@@ -7,41 +10,557 @@ struct Sentiment {
 
 impl Sentiment {
 
-    pub fn new(phrase: &str) -> Self {
-        let mut has_alpha = false;
-        let mut has_lower = false;
-        let mut has_non_ws = false;
-        let mut last_non_ws: Option<char> = None;
+    pub fn new(phrase: &str, rule: QuestionRule, is_blank: fn(char) -> bool) -> Self {
+        let mut scanner = Scanner::new(rule, is_blank);
 
         // Single pass
         for c in phrase.chars() {
-            if !c.is_whitespace() {
-                has_non_ws = true;
-                last_non_ws = Some(c);
+            scanner.feed(c);
+        }
+        scanner.finish()
+    }
+
+    /// Classify a message as it streams in, never holding more than one
+    /// chunk of it in memory.
+    pub fn from_reader(
+        mut reader: impl Read,
+        rule: QuestionRule,
+        is_blank: fn(char) -> bool,
+    ) -> io::Result<Self> {
+        let mut scanner = Scanner::new(rule, is_blank);
+        let mut buf = [0u8; 1024];
+        // Bytes of a UTF-8 sequence split across two reads wait at the
+        // front of `buf` until the rest of the character arrives.
+        let mut pending = 0;
+
+        loop {
+            let n = match reader.read(&mut buf[pending..]) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            let filled = pending + n;
+            let valid = match std::str::from_utf8(&buf[..filled]) {
+                Ok(text) => text,
+                Err(e) if e.error_len().is_none() => {
+                    // Truncated sequence at the end: decode what we can.
+                    std::str::from_utf8(&buf[..e.valid_up_to()]).unwrap()
+                }
+                Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            };
+            valid.chars().for_each(|c| scanner.feed(c));
+
+            let consumed = valid.len();
+            buf.copy_within(consumed..filled, 0);
+            pending = filled - consumed;
+        }
+
+        if pending > 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream ended inside a UTF-8 sequence",
+            ));
+        }
+        Ok(scanner.finish())
+    }
+
+    pub fn is_question(&self) -> bool {
+        self.is_question
+    }
+
+    pub fn is_yelling(&self) -> bool {
+        self.is_yelling
+    }
+
+    pub fn is_silent(&self) -> bool {
+        self.is_silent
+    }
+
+    pub fn kind(&self) -> ResponseKind {
+        ResponseKind::from(self)
+    }
+}
+
+/// A personality that turns a message's sentiment into a reply.
+///
+/// Bob is the default; implement this for Alice, a polite bot, or whoever
+/// else, and the same sentiment analysis feeds them all.
+pub trait Responder {
+    fn respond(&self, sentiment: &Sentiment) -> &str;
+}
+
+impl Responder for Bob<'_> {
+    fn respond(&self, sentiment: &Sentiment) -> &str {
+        self.reply_to(sentiment.kind())
+    }
+}
+
+/// Analyse a message with the default question and silence rules and let
+/// `responder` pick the reply.
+pub fn respond<'r, R: Responder + ?Sized>(responder: &'r R, message: &str) -> &'r str {
+    responder.respond(&Sentiment::new(message, QuestionRule::default(), is_blank))
+}
+
+/// Bob's default notion of silence: Unicode whitespace, plus the invisible
+/// format characters (general category Cf) such as zero-width spaces,
+/// direction marks and the byte order mark.
+pub fn is_blank(c: char) -> bool {
+    c.is_whitespace() || is_format_char(c)
+}
+
+/// Unicode general category Cf, as of Unicode 15.
+fn is_format_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{0600}'..='\u{0605}'
+            | '\u{061C}'
+            | '\u{06DD}'
+            | '\u{070F}'
+            | '\u{0890}'..='\u{0891}'
+            | '\u{08E2}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+            | '\u{110BD}'
+            | '\u{110CD}'
+            | '\u{13430}'..='\u{1343F}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0001}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// What counts as asking a question.
+///
+/// The default only accepts a trailing ASCII '?', exactly as Bob always has.
+/// Widen it for "?!", fullwidth '？' or a leading inverted '¿'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuestionRule<'a> {
+    terminators: &'a [char],
+    openers: &'a [char],
+    allow_trailing_punctuation: bool,
+}
+
+impl Default for QuestionRule<'_> {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+impl<'a> QuestionRule<'a> {
+    /// Only a trailing '?' makes a question.
+    pub const fn strict() -> Self {
+        Self {
+            terminators: &['?'],
+            openers: &[],
+            allow_trailing_punctuation: false,
+        }
+    }
+
+    /// Accepts '?' and '？', a leading '¿', and punctuation after the mark.
+    pub const fn lenient() -> Self {
+        Self {
+            terminators: &['?', '？'],
+            openers: &['¿'],
+            allow_trailing_punctuation: true,
+        }
+    }
+
+    /// Characters that end a question.
+    pub const fn terminators(mut self, terminators: &'a [char]) -> Self {
+        self.terminators = terminators;
+        self
+    }
+
+    /// Characters that open a question when they lead the message and nothing
+    /// else ends it, as in "¿Dónde está"; "¿Qué? No sé." isn't a question.
+    pub const fn openers(mut self, openers: &'a [char]) -> Self {
+        self.openers = openers;
+        self
+    }
+
+    /// Whether ASCII punctuation may follow the terminator, as in "What?!".
+    pub const fn allow_trailing_punctuation(mut self, allow: bool) -> Self {
+        self.allow_trailing_punctuation = allow;
+        self
+    }
+}
+
+/// The running state of a single pass over a message, one char at a time.
+///
+/// Positions are byte offsets into the message, so they slice it directly.
+#[derive(Debug)]
+struct Scanner<'r> {
+    rule: QuestionRule<'r>,
+    is_blank: fn(char) -> bool,
+    offset: usize,
+    first_alpha: Option<usize>,
+    first_lower: Option<usize>,
+    last_non_ws: Option<(usize, char)>,
+    opener: Option<usize>,
+    terminator: Option<usize>,
+    /// Whether anything after the opener ended a sentence, which means the
+    /// opener's question is already over.
+    closed: bool,
+}
+
+impl<'r> Scanner<'r> {
+    fn new(rule: QuestionRule<'r>, is_blank: fn(char) -> bool) -> Self {
+        Self {
+            rule,
+            is_blank,
+            offset: 0,
+            first_alpha: None,
+            first_lower: None,
+            last_non_ws: None,
+            opener: None,
+            terminator: None,
+            closed: false,
+        }
+    }
+
+    fn feed(&mut self, c: char) {
+        if !(self.is_blank)(c) {
+            if self.last_non_ws.is_none() && self.rule.openers.contains(&c) {
+                self.opener = Some(self.offset);
+            } else if matches!(c, '.' | '!' | '?') || self.rule.terminators.contains(&c) {
+                self.closed = true;
             }
-            if c.is_ascii_alphabetic() {
-                has_alpha = true;
-                has_lower |= c.is_ascii_lowercase();
+            if self.rule.terminators.contains(&c) {
+                self.terminator = Some(self.offset);
+            } else if !(self.rule.allow_trailing_punctuation && c.is_ascii_punctuation()) {
+                self.terminator = None;
             }
+            self.last_non_ws = Some((self.offset, c));
         }
+        if c.is_ascii_alphabetic() {
+            self.first_alpha.get_or_insert(self.offset);
+            if c.is_ascii_lowercase() {
+                self.first_lower.get_or_insert(self.offset);
+            }
+        }
+        self.offset += c.len_utf8();
+    }
 
-        Self {
-            is_silent: !has_non_ws,
-            is_question: last_non_ws == Some('?'),
-            is_yelling: has_alpha && !has_lower,
+    fn question_mark(&self) -> Option<usize> {
+        self.terminator.or(self.opener.filter(|_| !self.closed))
+    }
+
+    fn finish(self) -> Sentiment {
+        Sentiment {
+            is_silent: self.last_non_ws.is_none(),
+            is_question: self.question_mark().is_some(),
+            is_yelling: self.first_alpha.is_some() && self.first_lower.is_none(),
+        }
+    }
+
+    fn analysis(self) -> Analysis {
+        let question_mark = self.question_mark();
+        let last_non_whitespace = self.last_non_ws;
+        let (first_letter, first_lowercase) = (self.first_alpha, self.first_lower);
+        let sentiment = self.finish();
+        Analysis {
+            kind: ResponseKind::from(&sentiment),
+            is_silent: sentiment.is_silent,
+            is_question: sentiment.is_question,
+            is_yelling: sentiment.is_yelling,
+            question_mark,
+            last_non_whitespace,
+            first_letter,
+            first_lowercase,
         }
     }
 }
 
+/// The category Bob sorts every message into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResponseKind {
+    Question,
+    Yelling,
+    YellingQuestion,
+    Silence,
+    Other,
+}
 
+impl From<&Sentiment> for ResponseKind {
+    fn from(s: &Sentiment) -> Self {
+        match (s.is_silent, s.is_question, s.is_yelling) {
+            (true, _, _) => ResponseKind::Silence,
+            (false, true, false) => ResponseKind::Question,
+            (false, false, true) => ResponseKind::Yelling,
+            (false, true, true) => ResponseKind::YellingQuestion,
+            (false, false, false) => ResponseKind::Other,
+        }
+    }
+}
 
-pub fn reply(message: &str) -> &str {
-    let s = Sentiment::new(message);
-    match (s.is_silent, s.is_question, s.is_yelling) {
-        (false, true, false) => "Sure.",
-        (false, false, true) => "Whoa, chill out!",
-        (false, true, true) => "Calm down, I know what I'm doing!",
-        (false, false, false) => "Whatever.",
-        _ => "Fine. Be that way!",
+/// Classify a message without committing to any particular reply.
+pub fn classify(message: &str) -> ResponseKind {
+    DEFAULT_BOB.classify(message)
+}
+
+/// Why a message landed in the category it did.
+///
+/// All positions are byte offsets into the analysed message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    pub kind: ResponseKind,
+    pub is_silent: bool,
+    pub is_question: bool,
+    pub is_yelling: bool,
+    /// The question mark that made this a question, if any: the trailing
+    /// terminator, or failing that a leading inverted mark with no sentence
+    /// ending after it.
+    pub question_mark: Option<usize>,
+    /// The last character that isn't blank; `None` means silence.
+    pub last_non_whitespace: Option<(usize, char)>,
+    /// The first ASCII letter; without one there is nothing to yell.
+    pub first_letter: Option<usize>,
+    /// The first lowercase ASCII letter, which rules out yelling.
+    pub first_lowercase: Option<usize>,
+}
+
+/// Report which features fired for a message and where.
+pub fn explain(message: &str) -> Analysis {
+    DEFAULT_BOB.explain(message)
+}
+
+/// Split a message after each run of `.`, `!` or `?`, so "Really?!" stays one
+/// sentence. Surrounding whitespace is trimmed and blank pieces dropped;
+/// trailing text without a terminator still counts as a sentence.
+fn sentences(message: &str) -> impl Iterator<Item = &str> {
+    let is_terminator = |c: char| matches!(c, '.' | '!' | '?');
+    let mut rest = message;
+
+    std::iter::from_fn(move || {
+        loop {
+            if rest.is_empty() {
+                return None;
+            }
+            let mut end = rest.len();
+            let mut chars = rest.char_indices().peekable();
+            while let Some((i, c)) = chars.next() {
+                let next_is_terminator = chars.peek().is_some_and(|&(_, n)| is_terminator(n));
+                if is_terminator(c) && !next_is_terminator {
+                    end = i + c.len_utf8();
+                    break;
+                }
+            }
+            let (sentence, tail) = rest.split_at(end);
+            rest = tail;
+            let sentence = sentence.trim();
+            if !sentence.is_empty() {
+                return Some(sentence);
+            }
+        }
+    })
+}
+
+/// Bob's canned replies, one per response category, plus the rules he
+/// classifies messages by.
+///
+/// Build one with [`Bob::builder`] and override whichever replies or rules
+/// you care about.
+#[derive(Clone, Copy, Debug)]
+pub struct Bob<'a> {
+    question: &'a str,
+    yelling: &'a str,
+    yelling_question: &'a str,
+    silence: &'a str,
+    other: &'a str,
+    question_rule: QuestionRule<'a>,
+    is_blank: fn(char) -> bool,
+}
+
+/// The teenager we all know and love.
+const DEFAULT_BOB: Bob<'static> = Bob {
+    question: "Sure.",
+    yelling: "Whoa, chill out!",
+    yelling_question: "Calm down, I know what I'm doing!",
+    silence: "Fine. Be that way!",
+    other: "Whatever.",
+    question_rule: QuestionRule::strict(),
+    is_blank,
+};
+
+impl Default for Bob<'_> {
+    fn default() -> Self {
+        DEFAULT_BOB
+    }
+}
+
+impl<'a> Bob<'a> {
+    pub fn builder() -> BobBuilder<'a> {
+        BobBuilder::default()
+    }
+
+    pub fn classify(&self, message: &str) -> ResponseKind {
+        ResponseKind::from(&Sentiment::new(message, self.question_rule, self.is_blank))
+    }
+
+    pub fn explain(&self, message: &str) -> Analysis {
+        let mut scanner = Scanner::new(self.question_rule, self.is_blank);
+        message.chars().for_each(|c| scanner.feed(c));
+        scanner.analysis()
+    }
+
+    pub fn reply(&self, message: &str) -> &'a str {
+        self.reply_to(self.classify(message))
+    }
+
+    /// Reply to a message read from `reader`, without first collecting it
+    /// into a `String`.
+    pub fn reply_from_reader(&self, reader: impl Read) -> io::Result<&'a str> {
+        let sentiment = Sentiment::from_reader(reader, self.question_rule, self.is_blank)?;
+        Ok(self.reply_to(ResponseKind::from(&sentiment)))
+    }
+
+    /// Reply to each sentence of a message on its own.
+    pub fn reply_each<'m>(&self, message: &'m str) -> Vec<(&'m str, &'a str)> {
+        sentences(message)
+            .map(|sentence| (sentence, self.reply(sentence)))
+            .collect()
+    }
+
+    /// The reply for an already classified message.
+    pub fn reply_to(&self, kind: ResponseKind) -> &'a str {
+        match kind {
+            ResponseKind::Question => self.question,
+            ResponseKind::Yelling => self.yelling,
+            ResponseKind::YellingQuestion => self.yelling_question,
+            ResponseKind::Silence => self.silence,
+            ResponseKind::Other => self.other,
+        }
+    }
+}
+
+/// Overrides Bob's replies one category at a time; anything left unset
+/// keeps the default phrasing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BobBuilder<'a> {
+    bob: Bob<'a>,
+}
+
+impl<'a> BobBuilder<'a> {
+    pub fn on_question(mut self, reply: &'a str) -> Self {
+        self.bob.question = reply;
+        self
+    }
+
+    pub fn on_yelling(mut self, reply: &'a str) -> Self {
+        self.bob.yelling = reply;
+        self
+    }
+
+    pub fn on_yelling_question(mut self, reply: &'a str) -> Self {
+        self.bob.yelling_question = reply;
+        self
     }
+
+    pub fn on_silence(mut self, reply: &'a str) -> Self {
+        self.bob.silence = reply;
+        self
+    }
+
+    pub fn on_other(mut self, reply: &'a str) -> Self {
+        self.bob.other = reply;
+        self
+    }
+
+    pub fn question_rule(mut self, rule: QuestionRule<'a>) -> Self {
+        self.bob.question_rule = rule;
+        self
+    }
+
+    /// Which characters don't count as saying anything. A message made only
+    /// of these is silence, and they are skipped when looking for a trailing
+    /// question mark. Defaults to [`is_blank`].
+    pub fn silence(mut self, is_blank: fn(char) -> bool) -> Self {
+        self.bob.is_blank = is_blank;
+        self
+    }
+
+    pub fn build(self) -> Bob<'a> {
+        self.bob
+    }
+}
+
+/// How many identical hostile messages in a row before Bob changes his tune.
+const ESCALATE_AFTER: usize = 3;
+
+/// Bob's reply once a streak of yelling or silence has gone on too long.
+/// Other categories never escalate.
+fn escalated(kind: ResponseKind) -> Option<&'static str> {
+    match kind {
+        ResponseKind::Yelling => Some("I'm not listening until you stop shouting."),
+        ResponseKind::YellingQuestion => Some("Ask me again when you've calmed down."),
+        ResponseKind::Silence => Some("Fine. I'm leaving."),
+        ResponseKind::Question | ResponseKind::Other => None,
+    }
+}
+
+/// A running chat with Bob that remembers what came before.
+///
+/// Classification is unchanged; only the choice of reply looks at history.
+/// The same hostile category several times in a row escalates Bob's reply.
+#[derive(Clone, Debug, Default)]
+pub struct Conversation<'a> {
+    bob: Bob<'a>,
+    last: Option<ResponseKind>,
+    streak: usize,
+}
+
+impl<'a> Conversation<'a> {
+    pub fn new(bob: Bob<'a>) -> Self {
+        Self {
+            bob,
+            last: None,
+            streak: 0,
+        }
+    }
+
+    pub fn reply(&mut self, message: &str) -> &'a str {
+        let kind = self.bob.classify(message);
+        if self.last == Some(kind) {
+            self.streak += 1;
+        } else {
+            self.last = Some(kind);
+            self.streak = 1;
+        }
+
+        match escalated(kind) {
+            Some(reply) if self.streak >= ESCALATE_AFTER => reply,
+            _ => self.bob.reply_to(kind),
+        }
+    }
+
+    /// How many messages in a row have fallen into the current category.
+    pub fn streak(&self) -> usize {
+        self.streak
+    }
+
+    /// Forget the history, as if the conversation had just started.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.streak = 0;
+    }
+}
+
+pub fn reply(message: &str) -> &str {
+    DEFAULT_BOB.reply(message)
+}
+
+pub fn reply_from_reader(reader: impl Read) -> io::Result<&'static str> {
+    DEFAULT_BOB.reply_from_reader(reader)
+}
+
+pub fn reply_each(message: &str) -> Vec<(&str, &'static str)> {
+    DEFAULT_BOB.reply_each(message)
 }
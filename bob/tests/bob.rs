@@ -142,3 +142,305 @@ fn multiple_line_question() {
         "Sure."
     );
 }
+
+#[test]
+fn default_bob_matches_reply() {
+    let bob = Bob::default();
+    for message in ["WATCH OUT!", "4?", "WHAT?", "", "Hi there"] {
+        assert_eq!(bob.reply(message), reply(message));
+    }
+}
+
+#[test]
+fn builder_overrides_only_given_categories() {
+    let bob = Bob::builder()
+        .on_question("Maybe.")
+        .on_silence("Hello?")
+        .build();
+    assert_eq!(bob.reply("Are you there?"), "Maybe.");
+    assert_eq!(bob.reply("   "), "Hello?");
+    assert_eq!(bob.reply("WATCH OUT!"), "Whoa, chill out!");
+    assert_eq!(bob.reply("Hi there"), "Whatever.");
+}
+
+#[test]
+fn builder_overrides_every_category() {
+    let bob = Bob::builder()
+        .on_question("q")
+        .on_yelling("y")
+        .on_yelling_question("yq")
+        .on_silence("s")
+        .on_other("o")
+        .build();
+    assert_eq!(bob.reply("Really?"), "q");
+    assert_eq!(bob.reply("STOP"), "y");
+    assert_eq!(bob.reply("WHY?"), "yq");
+    assert_eq!(bob.reply("\t"), "s");
+    assert_eq!(bob.reply("fine"), "o");
+}
+
+#[test]
+fn classify_each_kind() {
+    assert_eq!(classify("How are you?"), ResponseKind::Question);
+    assert_eq!(classify("WATCH OUT!"), ResponseKind::Yelling);
+    assert_eq!(classify("WHAT'S GOING ON?"), ResponseKind::YellingQuestion);
+    assert_eq!(classify(" \t\n"), ResponseKind::Silence);
+    assert_eq!(classify("1, 2, 3"), ResponseKind::Other);
+}
+
+#[test]
+fn reply_to_kind_matches_reply() {
+    let bob = Bob::default();
+    let message = "Does this cryogenic chamber make me look fat?";
+    assert_eq!(bob.reply_to(classify(message)), reply(message));
+}
+
+#[test]
+fn reply_from_reader_matches_reply() {
+    for message in ["WATCH OUT!", "4?", "WHAT?", "", "  \n\t", "Hi there"] {
+        assert_eq!(reply_from_reader(message.as_bytes()).unwrap(), reply(message));
+    }
+}
+
+#[test]
+fn reply_from_reader_spans_many_chunks() {
+    let mut message = "a".repeat(5000);
+    message.push_str(&" ".repeat(3000));
+    message.push('?');
+    message.push_str(&"\n".repeat(2000));
+    assert_eq!(reply_from_reader(message.as_bytes()).unwrap(), "Sure.");
+}
+
+#[test]
+fn reply_from_reader_handles_split_multibyte_chars() {
+    // One byte of padding pushes every "é" across a chunk boundary somewhere.
+    let message = format!("x{}\u{3000}", "é".repeat(2000));
+    assert_eq!(reply_from_reader(message.as_bytes()).unwrap(), "Whatever.");
+}
+
+#[test]
+fn reply_from_reader_rejects_invalid_utf8() {
+    let bytes: &[u8] = &[b'h', b'i', 0xff, b'?'];
+    let err = reply_from_reader(bytes).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn reply_each_sentence() {
+    assert_eq!(
+        reply_each("Wait! Hang on. Are you going to be OK?"),
+        vec![
+            ("Wait!", "Whatever."),
+            ("Hang on.", "Whatever."),
+            ("Are you going to be OK?", "Sure."),
+        ]
+    );
+}
+
+#[test]
+fn reply_each_keeps_terminator_runs_together() {
+    assert_eq!(
+        reply_each("WHAT?! fine... ok"),
+        vec![
+            ("WHAT?!", "Whoa, chill out!"),
+            ("fine...", "Whatever."),
+            ("ok", "Whatever."),
+        ]
+    );
+}
+
+#[test]
+fn reply_each_of_silence_is_empty() {
+    assert!(reply_each("   \n ").is_empty());
+}
+
+#[test]
+fn explain_a_calm_question() {
+    let analysis = explain("You are, what, like 15?  ");
+    assert_eq!(analysis.kind, ResponseKind::Question);
+    assert!(analysis.is_question && !analysis.is_yelling && !analysis.is_silent);
+    assert_eq!(analysis.question_mark, Some(22));
+    assert_eq!(analysis.last_non_whitespace, Some((22, '?')));
+    assert_eq!(analysis.first_letter, Some(0));
+    assert_eq!(analysis.first_lowercase, Some(1));
+}
+
+#[test]
+fn explain_yelling_has_no_lowercase() {
+    let analysis = explain("1, 2, 3 GO!");
+    assert_eq!(analysis.kind, ResponseKind::Yelling);
+    assert_eq!(analysis.first_letter, Some(8));
+    assert_eq!(analysis.first_lowercase, None);
+    assert_eq!(analysis.question_mark, None);
+}
+
+#[test]
+fn explain_silence() {
+    let analysis = explain(" \t ");
+    assert_eq!(analysis.kind, ResponseKind::Silence);
+    assert_eq!(analysis.last_non_whitespace, None);
+    assert_eq!(analysis.first_letter, None);
+}
+
+#[test]
+fn conversation_escalates_on_third_silence() {
+    let mut chat = Conversation::default();
+    assert_eq!(chat.reply(""), "Fine. Be that way!");
+    assert_eq!(chat.reply("  "), "Fine. Be that way!");
+    assert_eq!(chat.reply("\t"), "Fine. I'm leaving.");
+    assert_eq!(chat.streak(), 3);
+}
+
+#[test]
+fn conversation_escalates_repeated_yelling() {
+    let mut chat = Conversation::default();
+    assert_eq!(chat.reply("STOP"), "Whoa, chill out!");
+    assert_eq!(chat.reply("STOP IT"), "Whoa, chill out!");
+    assert_eq!(chat.reply("STOP IT NOW"), "I'm not listening until you stop shouting.");
+    assert_eq!(chat.reply("I SAID STOP"), "I'm not listening until you stop shouting.");
+}
+
+#[test]
+fn conversation_streak_breaks_on_a_different_category() {
+    let mut chat = Conversation::default();
+    chat.reply("");
+    chat.reply("");
+    assert_eq!(chat.reply("hello"), "Whatever.");
+    assert_eq!(chat.streak(), 1);
+    assert_eq!(chat.reply(""), "Fine. Be that way!");
+}
+
+#[test]
+fn conversation_does_not_escalate_questions() {
+    let mut chat = Conversation::default();
+    for _ in 0..5 {
+        assert_eq!(chat.reply("Why?"), "Sure.");
+    }
+}
+
+#[test]
+fn conversation_reset_forgets_history() {
+    let mut chat = Conversation::new(Bob::builder().on_silence("...").build());
+    chat.reply("");
+    chat.reply("");
+    chat.reset();
+    assert_eq!(chat.reply(""), "...");
+    assert_eq!(chat.streak(), 1);
+}
+
+#[test]
+fn strict_rule_ignores_punctuation_after_question_mark() {
+    assert_eq!(classify("Really?!"), ResponseKind::Other);
+    assert_eq!(classify("Really？"), ResponseKind::Other);
+}
+
+#[test]
+fn lenient_rule_accepts_wider_questions() {
+    let bob = Bob::builder()
+        .question_rule(QuestionRule::lenient())
+        .build();
+    assert_eq!(bob.classify("Really?!"), ResponseKind::Question);
+    assert_eq!(bob.classify("Really？"), ResponseKind::Question);
+    assert_eq!(bob.classify("  ¿Dónde está la biblioteca"), ResponseKind::Question);
+    assert_eq!(bob.classify("WHAT?!!"), ResponseKind::YellingQuestion);
+    assert_eq!(bob.classify("Ending with ? means a question."), ResponseKind::Other);
+}
+
+#[test]
+fn opener_does_not_outlast_its_sentence() {
+    let bob = Bob::builder()
+        .question_rule(QuestionRule::lenient())
+        .build();
+    assert_eq!(bob.classify("¿Qué? No sé."), ResponseKind::Other);
+    assert_eq!(bob.classify("¿Qué pasa. Nada"), ResponseKind::Other);
+    assert_eq!(bob.classify("¿QUÉ! NO!"), ResponseKind::Yelling);
+    assert_eq!(bob.classify("¿Qué? ¿No sé?"), ResponseKind::Question);
+    assert_eq!(bob.explain("¿Qué? No sé.").question_mark, None);
+    assert_eq!(bob.explain("¿Qué pasa").question_mark, Some(0));
+}
+
+#[test]
+fn custom_terminators() {
+    let bob = Bob::builder()
+        .question_rule(QuestionRule::strict().terminators(&['?', '‽']))
+        .build();
+    assert_eq!(bob.reply("you did what‽"), "Sure.");
+    assert_eq!(bob.reply_from_reader("you did what‽".as_bytes()).unwrap(), "Sure.");
+}
+
+#[test]
+fn explain_reports_the_terminator_not_the_trailing_punctuation() {
+    let bob = Bob::builder()
+        .question_rule(QuestionRule::lenient())
+        .build();
+    let analysis = bob.explain("Why?!");
+    assert_eq!(analysis.question_mark, Some(3));
+    assert_eq!(analysis.last_non_whitespace, Some((4, '!')));
+}
+
+struct PoliteBot;
+
+impl Responder for PoliteBot {
+    fn respond(&self, sentiment: &Sentiment) -> &str {
+        if sentiment.is_silent() {
+            "Take your time."
+        } else if sentiment.is_question() {
+            "Let me look into that for you."
+        } else {
+            "Thank you for sharing."
+        }
+    }
+}
+
+#[test]
+fn bob_is_the_default_responder() {
+    for message in ["WATCH OUT!", "4?", "WHAT?", "", "Hi there"] {
+        assert_eq!(respond(&Bob::default(), message), reply(message));
+    }
+}
+
+#[test]
+fn alternative_responder_reuses_sentiment() {
+    assert_eq!(respond(&PoliteBot, "Are you open?"), "Let me look into that for you.");
+    assert_eq!(respond(&PoliteBot, "WHAT?"), "Let me look into that for you.");
+    assert_eq!(respond(&PoliteBot, "  "), "Take your time.");
+    assert_eq!(respond(&PoliteBot, "HELLO"), "Thank you for sharing.");
+}
+
+#[test]
+fn responders_are_object_safe() {
+    let responders: [&dyn Responder; 2] = [&PoliteBot, &Bob::default()];
+    let replies: Vec<_> = responders.iter().map(|r| respond(*r, "")).collect();
+    assert_eq!(replies, ["Take your time.", "Fine. Be that way!"]);
+}
+
+#[test]
+fn sentiment_kind() {
+    let sentiment = Sentiment::new("WHAT?", QuestionRule::default(), is_blank);
+    assert!(sentiment.is_question() && sentiment.is_yelling() && !sentiment.is_silent());
+    assert_eq!(sentiment.kind(), ResponseKind::YellingQuestion);
+}
+
+#[test]
+fn invisible_format_characters_are_silence() {
+    assert_eq!(reply("   \u{200B} "), "Fine. Be that way!");
+    assert_eq!(reply("\u{FEFF}\u{2060}"), "Fine. Be that way!");
+    assert_eq!(reply_from_reader("\u{200D}\t".as_bytes()).unwrap(), "Fine. Be that way!");
+}
+
+#[test]
+fn invisible_characters_after_question_mark_are_skipped() {
+    assert_eq!(reply("Are you there?\u{200B}"), "Sure.");
+}
+
+#[test]
+fn custom_silence_predicate() {
+    let bob = Bob::builder()
+        .silence(|c| c.is_whitespace() || c.is_control() || c == '.')
+        .build();
+    assert_eq!(bob.reply("\u{0007} ... "), "Fine. Be that way!");
+    assert_eq!(bob.reply("Really?.."), "Sure.");
+    // Strict whitespace treats zero-width spaces as content again.
+    let strict = Bob::builder().silence(char::is_whitespace).build();
+    assert_eq!(strict.reply("\u{200B}"), "Whatever.");
+}
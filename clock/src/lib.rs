@@ -1,28 +1,850 @@
 use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+use std::time::Duration;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Clocks order by time since midnight, so `00:00` comes first and `23:59`
+/// last.
+///
+/// Days are 24 hours unless [`with_day_length`](Clock::with_day_length)
+/// says otherwise. Clocks with different day lengths are never equal, and
+/// anything comparing two clocks goes by the day of the one it's called on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct Clock {
-    minutes: i32,
+    /// Since midnight, always within the day.
+    seconds: i32,
+    /// Seconds in this clock's day.
+    day: i32,
 }
 
-const MINUTES_IN_DAY: i32 = 60 * 24;
+const SECONDS_IN_DAY: i64 = 60 * 60 * 24;
 
 impl Clock {
+    pub const fn new(hours: i32, minutes: i32) -> Self {
+        Self::new_hms(hours, minutes, 0)
+    }
+
+    /// Like [`new`](Self::new), down to the second. Seconds roll over into
+    /// minutes the same way minutes roll over into hours.
+    pub const fn new_hms(hours: i32, minutes: i32, seconds: i32) -> Self {
+        Self::from_seconds((hours as i64 * 60 + minutes as i64) * 60 + seconds as i64)
+    }
+
+    const fn from_seconds(seconds: i64) -> Self {
+        Self {
+            seconds: seconds.rem_euclid(SECONDS_IN_DAY) as i32,
+            day: SECONDS_IN_DAY as i32,
+        }
+    }
+
+    /// `seconds` since midnight, wrapped into this clock's day.
+    const fn wrap(&self, seconds: i64) -> Self {
+        Self {
+            seconds: seconds.rem_euclid(self.day as i64) as i32,
+            day: self.day,
+        }
+    }
+
+    /// The same time on a clock whose days are `minutes_in_day` long, for
+    /// Mars sols, shift clocks and game worlds. Rolls over if it's past
+    /// the end of the new day.
+    ///
+    /// Panics unless `minutes_in_day` is positive and no more than a
+    /// `u16`'s worth, about 45 days.
+    pub const fn with_day_length(&self, minutes_in_day: i32) -> Self {
+        assert!(
+            minutes_in_day > 0 && minutes_in_day <= u16::MAX as i32,
+            "days must be from 1 to 65535 minutes long"
+        );
+        Self {
+            seconds: self.seconds,
+            day: minutes_in_day * 60,
+        }
+        .wrap(self.seconds as i64)
+    }
+
+    /// Minutes in this clock's day: 1440 unless it's been changed.
+    pub const fn day_length(&self) -> i32 {
+        self.day / 60
+    }
+
+    pub const fn hours(&self) -> i32 {
+        self.seconds / 3600
+    }
+
+    pub const fn minutes(&self) -> i32 {
+        self.seconds / 60 % 60
+    }
+
+    pub const fn seconds(&self) -> i32 {
+        self.seconds % 60
+    }
+
+    /// Whole minutes since midnight.
+    pub const fn total_minutes(&self) -> i32 {
+        self.seconds / 60
+    }
+
+    /// For [`clock!`]: the time from the digits of each part, panicking,
+    /// which in a constant is a compile error, if they aren't in range.
+    #[doc(hidden)]
+    pub const fn from_literal(hours: &str, minutes: &str, seconds: &str) -> Self {
+        let (hours, minutes, seconds) = (digits(hours), digits(minutes), digits(seconds));
+        assert!(hours < 24, "hours must be from 0 to 23");
+        assert!(minutes < 60, "minutes must be from 00 to 59");
+        assert!(seconds < 60, "seconds must be from 00 to 59");
+        Self::new_hms(hours, minutes, seconds)
+    }
+
+    pub const fn add_hours(&self, hours: i32) -> Self {
+        self.wrap(self.seconds as i64 + hours as i64 * 3600)
+    }
+
+    pub const fn add_minutes(&self, minutes: i32) -> Self {
+        self.wrap(self.seconds as i64 + minutes as i64 * 60)
+    }
+
+    pub const fn add_seconds(&self, seconds: i32) -> Self {
+        self.wrap(self.seconds as i64 + seconds as i64)
+    }
+
+    /// This wall-clock time shifted by `offset`, e.g. from UTC into a zone
+    /// `offset` ahead of it. Plain minutes work too.
+    pub fn with_offset(&self, offset: impl Into<Offset>) -> Self {
+        self.add_minutes(offset.into().minutes)
+    }
+
+    /// This wall-clock time in zone `from`, as read in zone `to`.
+    pub fn convert(&self, from: Offset, to: Offset) -> Self {
+        self.add_minutes(to.minutes - from.minutes)
+    }
+
+    /// [`add_minutes`](Self::add_minutes), also saying how many times that
+    /// went past midnight: negative when going back past it.
+    pub const fn overflowing_add_minutes(&self, minutes: i32) -> (Self, i64) {
+        let total = self.seconds as i64 + minutes as i64 * 60;
+        (self.wrap(total), total.div_euclid(self.day as i64))
+    }
+
+    /// This time, then every `step_minutes` after it, round and round the
+    /// clock forever. A negative step goes back in time.
+    pub fn ticks(&self, step_minutes: i32) -> impl Iterator<Item = Clock> + use<> {
+        std::iter::successors(Some(*self), move |clock| {
+            Some(clock.add_minutes(step_minutes))
+        })
+    }
+
+    /// How many whole minutes from here, going forward, until the clock
+    /// reads `other`; past midnight if need be. From 0 up to a day less a
+    /// minute.
+    pub fn minutes_until(&self, other: &Clock) -> i32 {
+        self.seconds_until(other) / 60
+    }
+
+    /// The shortest way from here to `other`, in whole minutes: positive
+    /// going forward, negative going back. Twelve hours apart counts as
+    /// forward, as does half of whatever the day length is.
+    pub fn signed_difference(&self, other: &Clock) -> i32 {
+        let forward = self.seconds_until(other);
+        let shortest = if forward > self.day / 2 {
+            forward - self.day
+        } else {
+            forward
+        };
+        shortest / 60
+    }
+
+    /// The time on a 12-hour clock, like `2:05 PM`. Midnight is `12:00 AM`
+    /// and noon `12:00 PM`.
+    pub fn format_12h(&self) -> String {
+        format!(
+            "{}:{:02} {}",
+            self.twelve_hour(),
+            self.minutes(),
+            self.period()
+        )
+    }
+
+    /// The hour on a 12-hour clock, from 1 to 12.
+    fn twelve_hour(&self) -> i32 {
+        match self.hours() % 12 {
+            0 => 12,
+            h => h,
+        }
+    }
+
+    fn period(&self) -> &'static str {
+        if self.hours() < 12 { "AM" } else { "PM" }
+    }
+
+    /// Whether this is from `start` up to, but not including, `end`,
+    /// going forward; so `22:00` to `06:00` covers the night. See
+    /// [`ClockRange`].
+    pub fn is_between(&self, start: Clock, end: Clock) -> bool {
+        ClockRange::new(start, end).contains(*self)
+    }
+
+    /// Snapped to a multiple of `n_minutes` past midnight, rounding as
+    /// `mode` says. Rounding up past the last step of the day lands on
+    /// midnight, even when the day isn't a whole number of steps long.
+    ///
+    /// Panics if `n_minutes` isn't positive.
+    pub fn round_to(&self, n_minutes: i32, mode: RoundMode) -> Self {
+        assert!(
+            n_minutes > 0,
+            "can only round to a positive number of minutes"
+        );
+        let step = n_minutes as i64 * 60;
+        let seconds = self.seconds as i64;
+        let down = seconds - seconds % step;
+        let rounded = match mode {
+            RoundMode::Down => down,
+            RoundMode::Up if down == seconds => down,
+            RoundMode::Up => down + step,
+            RoundMode::Nearest if (seconds - down) * 2 < step => down,
+            RoundMode::Nearest => down + step,
+        };
+        // Wrapping a step that overshoots a custom day would land past midnight.
+        self.wrap(if rounded >= self.day as i64 {
+            0
+        } else {
+            rounded
+        })
+    }
+
+    /// The time laid out like `strftime` would:
+    ///
+    /// - `%H`: hour, `00` to `23`
+    /// - `%k`: hour, ` 0` to `23`, padded with a space
+    /// - `%I`: hour on a 12-hour clock, `01` to `12`
+    /// - `%p`: `AM` or `PM`
+    /// - `%M`: minute, `00` to `59`
+    /// - `%S`: second, `00` to `59`
+    /// - `%%`: a `%`
+    ///
+    /// Anything else, unknown `%` codes included, is copied as it is.
+    pub fn format(&self, pattern: &str) -> String {
+        let mut out = String::with_capacity(pattern.len());
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('H') => out.push_str(&format!("{:02}", self.hours())),
+                Some('k') => out.push_str(&format!("{:2}", self.hours())),
+                Some('I') => out.push_str(&format!("{:02}", self.twelve_hour())),
+                Some('p') => out.push_str(self.period()),
+                Some('M') => out.push_str(&format!("{:02}", self.minutes())),
+                Some('S') => out.push_str(&format!("{:02}", self.seconds())),
+                Some('%') => out.push('%'),
+                Some(other) => {
+                    out.push('%');
+                    out.push(other);
+                }
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+
+    fn seconds_until(&self, other: &Clock) -> i32 {
+        (other.seconds - self.seconds).rem_euclid(self.day)
+    }
+}
+
+/// `s` as a number, for [`clock!`]: one or two ASCII digits.
+const fn digits(s: &str) -> i32 {
+    let bytes = s.as_bytes();
+    assert!(
+        !bytes.is_empty() && bytes.len() <= 2,
+        "expected one or two digits"
+    );
+    let mut n = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(bytes[i].is_ascii_digit(), "expected one or two digits");
+        n = n * 10 + (bytes[i] - b'0') as i32;
+        i += 1;
+    }
+    n
+}
+
+/// A [`Clock`] written the way it reads, checked at compile time:
+/// `clock!(10:30)` or `clock!(23:59:59)`. Works in `const` and `static`
+/// items.
+///
+/// ```compile_fail
+/// let bedtime = clock::clock!(24:30);
+/// ```
+#[macro_export]
+macro_rules! clock {
+    ($hours:tt : $minutes:tt) => {
+        $crate::clock!($hours : $minutes : 0)
+    };
+    ($hours:tt : $minutes:tt : $seconds:tt) => {
+        const {
+            $crate::Clock::from_literal(stringify!($hours), stringify!($minutes), stringify!($seconds))
+        }
+    };
+}
+
+/// Which way [`Clock::round_to`] goes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoundMode {
+    /// To the closer step, going up from exactly halfway.
+    Nearest,
+    Up,
+    Down,
+}
+
+/// Whole seconds of `duration`, less any whole days of `clock`'s, which
+/// it can't see anyway.
+fn seconds_of(duration: Duration, clock: &Clock) -> i64 {
+    (duration.as_secs() % clock.day as u64) as i64
+}
+
+/// Anything under a second is dropped.
+impl Add<Duration> for Clock {
+    type Output = Clock;
+
+    fn add(self, duration: Duration) -> Clock {
+        self.wrap(self.seconds as i64 + seconds_of(duration, &self))
+    }
+}
+
+impl Sub<Duration> for Clock {
+    type Output = Clock;
+
+    fn sub(self, duration: Duration) -> Clock {
+        self.wrap(self.seconds as i64 - seconds_of(duration, &self))
+    }
+}
+
+/// Adds minutes, like [`Clock::add_minutes`].
+impl Add<i32> for Clock {
+    type Output = Clock;
+
+    fn add(self, minutes: i32) -> Clock {
+        self.add_minutes(minutes)
+    }
+}
+
+/// Takes away minutes.
+impl Sub<i32> for Clock {
+    type Output = Clock;
+
+    fn sub(self, minutes: i32) -> Clock {
+        self.wrap(self.seconds as i64 - minutes as i64 * 60)
+    }
+}
+
+impl AddAssign<Duration> for Clock {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl SubAssign<Duration> for Clock {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+impl AddAssign<i32> for Clock {
+    fn add_assign(&mut self, minutes: i32) {
+        *self = *self + minutes;
+    }
+}
+
+impl SubAssign<i32> for Clock {
+    fn sub_assign(&mut self, minutes: i32) {
+        *self = *self - minutes;
+    }
+}
+
+/// How far a time zone is ahead of UTC, or behind it when negative.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Offset {
+    minutes: i32,
+}
+
+impl Offset {
+    pub const UTC: Offset = Offset { minutes: 0 };
+
+    /// `Offset::new(5, 30)` is UTC+05:30. For zones behind UTC both parts
+    /// are negative: `Offset::new(-3, -30)` is UTC-03:30.
     pub fn new(hours: i32, minutes: i32) -> Self {
-        let total = hours * 60 + minutes;
         Self {
-            minutes: total.rem_euclid(MINUTES_IN_DAY),
+            minutes: hours * 60 + minutes,
         }
     }
 
+    pub fn minutes(&self) -> i32 {
+        self.minutes
+    }
+}
+
+impl From<i32> for Offset {
+    fn from(minutes: i32) -> Self {
+        Self { minutes }
+    }
+}
+
+/// `+05:30`, `-03:00`, and `+00:00` for UTC.
+impl Display for Offset {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let sign = if self.minutes < 0 { '-' } else { '+' };
+        let minutes = self.minutes.unsigned_abs();
+        write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+/// A [`Clock`] that remembers which day it's on, counting from day 0, so
+/// running past midnight moves on to the next day rather than being
+/// forgotten. Orders by day, then by time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct DatedClock {
+    day: i64,
+    clock: Clock,
+}
+
+impl DatedClock {
+    pub fn new(day: i64, clock: Clock) -> Self {
+        Self { day, clock }
+    }
+
+    pub fn day(&self) -> i64 {
+        self.day
+    }
+
+    pub fn clock(&self) -> Clock {
+        self.clock
+    }
+
     pub fn add_minutes(&self, minutes: i32) -> Self {
-        Self::new(0, self.minutes + minutes)
+        let (clock, days) = self.clock.overflowing_add_minutes(minutes);
+        Self::new(self.day + days, clock)
     }
 }
 
+/// `day 1, 00:20`.
+impl Display for DatedClock {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "day {}, ", self.day)?;
+        self.clock.fmt(f)
+    }
+}
+
+/// [`Clock::total_minutes`]; the seconds are dropped.
+impl From<Clock> for u32 {
+    fn from(clock: Clock) -> u32 {
+        clock.total_minutes() as u32
+    }
+}
+
+/// Minutes since midnight weren't less than a day's worth.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MinutesOutOfRange(pub u32);
+
+impl Display for MinutesOutOfRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} minutes is past the end of the day", self.0)
+    }
+}
+
+impl std::error::Error for MinutesOutOfRange {}
+
+/// From minutes since midnight. Unlike [`Clock::new`], nothing rolls over.
+impl TryFrom<u32> for Clock {
+    type Error = MinutesOutOfRange;
+
+    fn try_from(minutes: u32) -> Result<Self, Self::Error> {
+        if (minutes as i64) * 60 < SECONDS_IN_DAY {
+            Ok(Self::new(0, minutes as i32))
+        } else {
+            Err(MinutesOutOfRange(minutes))
+        }
+    }
+}
+
+/// Drops anything under a second.
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveTime> for Clock {
+    fn from(time: chrono::NaiveTime) -> Self {
+        use chrono::Timelike;
+        Self::from_seconds(time.num_seconds_from_midnight() as i64)
+    }
+}
+
+/// A clock with a day longer than 24 hours wraps round, so 25:00 is 01:00.
+#[cfg(feature = "chrono")]
+impl From<Clock> for chrono::NaiveTime {
+    fn from(clock: Clock) -> Self {
+        let seconds = clock.seconds as i64 % SECONDS_IN_DAY;
+        chrono::NaiveTime::from_num_seconds_from_midnight_opt(seconds as u32, 0)
+            .expect("a clock is always within the day")
+    }
+}
+
+/// Drops anything under a second.
+#[cfg(feature = "time")]
+impl From<time::Time> for Clock {
+    fn from(time: time::Time) -> Self {
+        let (h, m, s) = time.as_hms();
+        Self::new_hms(h as i32, m as i32, s as i32)
+    }
+}
+
+/// A clock with a day longer than 24 hours wraps round, so 25:00 is 01:00.
+#[cfg(feature = "time")]
+impl From<Clock> for time::Time {
+    fn from(clock: Clock) -> Self {
+        let clock = Clock::from_seconds(clock.seconds as i64);
+        time::Time::from_hms(
+            clock.hours() as u8,
+            clock.minutes() as u8,
+            clock.seconds() as u8,
+        )
+        .expect("a clock is always within the day")
+    }
+}
+
+/// As the [`Display`] string, `HH:MM`, or `HH:MM:SS` if there are seconds
+/// to keep.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Clock {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.seconds() == 0 {
+            serializer.collect_str(self)
+        } else {
+            serializer.collect_str(&format_args!("{self:#}"))
+        }
+    }
+}
+
+/// Anything [`FromStr`] takes, failing with its error message otherwise.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Clock {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// `HH:MM`, leaving off the seconds; the alternate form, `{:#}`, is
+/// `HH:MM:SS`.
 impl Display for Clock {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let (h, m) = (self.minutes / 60, self.minutes % 60);
-        write!(f, "{h:02}:{m:02}")
+        let (h, m, s) = (self.hours(), self.minutes(), self.seconds());
+        if f.alternate() {
+            write!(f, "{h:02}:{m:02}:{s:02}")
+        } else {
+            write!(f, "{h:02}:{m:02}")
+        }
+    }
+}
+
+/// Why a string isn't a time of day.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseClockError {
+    /// Not `H:MM` or `HH:MM`, with an optional `:SS`.
+    Format,
+    /// Hours are one or two digits, from 0 to 23.
+    InvalidHours(String),
+    /// Minutes are two digits, from 00 to 59.
+    InvalidMinutes(String),
+    /// Seconds are two digits, from 00 to 59.
+    InvalidSeconds(String),
+}
+
+impl Display for ParseClockError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseClockError::Format => f.write_str("expected a time like 09:30"),
+            ParseClockError::InvalidHours(h) => write!(f, "{h:?} isn't an hour from 0 to 23"),
+            ParseClockError::InvalidMinutes(m) => write!(f, "{m:?} isn't a minute from 00 to 59"),
+            ParseClockError::InvalidSeconds(s) => write!(f, "{s:?} isn't a second from 00 to 59"),
+        }
+    }
+}
+
+impl std::error::Error for ParseClockError {}
+
+/// The other way from [`Display`]: `HH:MM` or `H:MM`, optionally with
+/// `:SS`. Unlike [`Clock::new`], nothing rolls over; `24:00` is an error.
+impl FromStr for Clock {
+    type Err = ParseClockError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let (Some(h), Some(m), secs, None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ParseClockError::Format);
+        };
+        let hours =
+            component(h, 1..=2, 23).ok_or_else(|| ParseClockError::InvalidHours(h.to_string()))?;
+        let minutes = component(m, 2..=2, 59)
+            .ok_or_else(|| ParseClockError::InvalidMinutes(m.to_string()))?;
+        let seconds = match secs {
+            Some(secs) => component(secs, 2..=2, 59)
+                .ok_or_else(|| ParseClockError::InvalidSeconds(secs.to_string()))?,
+            None => 0,
+        };
+        Ok(Self::new_hms(hours, minutes, seconds))
+    }
+}
+
+/// `digits` as a number up to `max`, if it's all ASCII digits and the
+/// right length.
+fn component(digits: &str, len: std::ops::RangeInclusive<usize>, max: i32) -> Option<i32> {
+    if !len.contains(&digits.len()) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|&n| n <= max)
+}
+
+/// The times from `start` up to, but not including, `end`, going forward
+/// and past midnight if `end` comes earlier in the day. With `start` and
+/// `end` the same, it's empty.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ClockRange {
+    pub start: Clock,
+    pub end: Clock,
+}
+
+impl ClockRange {
+    pub fn new(start: Clock, end: Clock) -> Self {
+        Self { start, end }
+    }
+
+    pub fn contains(&self, clock: Clock) -> bool {
+        self.start.seconds_until(&clock) < self.start.seconds_until(&self.end)
+    }
+
+    /// Whole minutes from `start` to `end`.
+    pub fn duration_minutes(&self) -> i32 {
+        self.start.minutes_until(&self.end)
+    }
+}
+
+/// A time something should happen every day, going off either just the
+/// once or, like `*/15` in cron, every so many minutes from then until
+/// midnight.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Alarm {
+    start: Clock,
+    every_minutes: Option<i32>,
+}
+
+impl Alarm {
+    pub fn at(start: Clock) -> Self {
+        Self {
+            start,
+            every_minutes: None,
+        }
+    }
+
+    /// Off at `start`, then every `every_minutes` until the end of the day.
+    ///
+    /// Panics if `every_minutes` isn't positive.
+    pub fn recurring(start: Clock, every_minutes: i32) -> Self {
+        assert!(every_minutes > 0, "alarms can only repeat going forward");
+        Self {
+            start,
+            every_minutes: Some(every_minutes),
+        }
+    }
+
+    /// When this next goes off strictly after `clock`, tomorrow if need be.
+    pub fn next_after(&self, clock: Clock) -> Clock {
+        if clock < self.start {
+            return self.start;
+        }
+        if let Some(every) = self.every_minutes {
+            let period = every as i64 * 60;
+            let elapsed = (clock.seconds - self.start.seconds) as i64;
+            let next = self.start.seconds as i64 + (elapsed / period + 1) * period;
+            if next < self.start.day as i64 {
+                return self.start.wrap(next);
+            }
+        }
+        self.start
+    }
+}
+
+/// A set of [`Alarm`]s to ask what's coming up.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Schedule {
+    alarms: Vec<Alarm>,
+}
+
+impl Schedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn alarm(mut self, alarm: Alarm) -> Self {
+        self.alarms.push(alarm);
+        self
+    }
+
+    pub fn add(&mut self, alarm: Alarm) {
+        self.alarms.push(alarm);
+    }
+
+    /// The soonest any alarm goes off strictly after `clock`, wrapping
+    /// round to tomorrow. `None` with no alarms.
+    pub fn next_after(&self, clock: Clock) -> Option<Clock> {
+        self.alarms
+            .iter()
+            .map(|alarm| alarm.next_after(clock))
+            .min_by_key(|next| forward(clock, *next))
+    }
+
+    /// Every time an alarm goes off after `start` and up to and including
+    /// `end`, in order, going forward from `start` and past midnight if
+    /// `end` is earlier in the day. Empty if they're the same time.
+    pub fn due_between(&self, start: Clock, end: Clock) -> Vec<Clock> {
+        let window = start.seconds_until(&end);
+        let mut due: Vec<(i32, Clock)> = self
+            .alarms
+            .iter()
+            .flat_map(|alarm| {
+                std::iter::successors(Some(alarm.next_after(start)), |&at| {
+                    Some(alarm.next_after(at))
+                })
+                .map(|at| (forward(start, at), at))
+                .scan(0, |last, (distance, at)| {
+                    // Once round the whole day, it's all repeats.
+                    let later = distance > *last;
+                    *last = distance;
+                    later.then_some((distance, at))
+                })
+                .take_while(move |&(distance, _)| distance <= window)
+            })
+            .collect();
+        due.sort_unstable();
+        due.dedup();
+        due.into_iter().map(|(_, at)| at).collect()
+    }
+}
+
+/// Seconds on from `from` to `to`, with the same time meaning a whole day.
+fn forward(from: Clock, to: Clock) -> i32 {
+    match from.seconds_until(&to) {
+        0 => from.day,
+        seconds => seconds,
+    }
+}
+
+/// Times how long things take by reading a [`Clock`] now and then.
+///
+/// A clock can't tell one day from the next, so each reading is taken to
+/// be less than a day after the last one. Read it at least once a day and
+/// it keeps count across as many midnights as it likes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stopwatch {
+    started: Clock,
+    last: Clock,
+    /// Seconds from `started` to `last`.
+    elapsed: u64,
+    lap_started: u64,
+    laps: Vec<Duration>,
+}
+
+impl Stopwatch {
+    pub fn start_at(clock: Clock) -> Self {
+        Self {
+            started: clock,
+            last: clock,
+            elapsed: 0,
+            lap_started: 0,
+            laps: Vec::new(),
+        }
+    }
+
+    pub fn started(&self) -> Clock {
+        self.started
+    }
+
+    /// The time since starting, with the clock now reading `clock`.
+    pub fn elapsed_at(&mut self, clock: Clock) -> Duration {
+        self.elapsed += self.last.seconds_until(&clock) as u64;
+        self.last = clock;
+        Duration::from_secs(self.elapsed)
+    }
+
+    /// Ends a lap at `clock` and starts the next, giving back how long the
+    /// lap took.
+    pub fn lap(&mut self, clock: Clock) -> Duration {
+        self.elapsed_at(clock);
+        let lap = Duration::from_secs(self.elapsed - self.lap_started);
+        self.lap_started = self.elapsed;
+        self.laps.push(lap);
+        lap
+    }
+
+    /// Every lap so far, first to last.
+    pub fn laps(&self) -> &[Duration] {
+        &self.laps
+    }
+}
+
+/// Somewhere to read the time of day from: the system clock, or a fake
+/// one in tests.
+#[cfg(feature = "std-time")]
+pub trait TimeSource {
+    fn now(&self) -> Clock;
+}
+
+/// The system clock, shifted by `offset`. The standard library only knows
+/// UTC, so give it the local zone's offset to get local time.
+#[cfg(feature = "std-time")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SystemClock {
+    pub offset: Offset,
+}
+
+#[cfg(feature = "std-time")]
+impl TimeSource for SystemClock {
+    fn now(&self) -> Clock {
+        let since_epoch = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        (Clock::new(0, 0) + since_epoch).with_offset(self.offset)
+    }
+}
+
+/// A stopped clock: always this time.
+#[cfg(feature = "std-time")]
+impl TimeSource for Clock {
+    fn now(&self) -> Clock {
+        *self
+    }
+}
+
+#[cfg(feature = "std-time")]
+impl<F: Fn() -> Clock> TimeSource for F {
+    fn now(&self) -> Clock {
+        self()
+    }
+}
+
+#[cfg(feature = "std-time")]
+impl Clock {
+    /// The wall-clock time now where the clock on the wall is `offset` from
+    /// UTC. The standard library has no idea of the local zone, so the
+    /// caller says what it is; [`Offset::UTC`] gives UTC.
+    pub fn now(offset: impl Into<Offset>) -> Self {
+        Self::now_from(&SystemClock {
+            offset: offset.into(),
+        })
+    }
+
+    pub fn now_from(source: &impl TimeSource) -> Self {
+        source.now()
     }
 }
@@ -287,3 +287,823 @@ fn clocks_with_negative_hours_and_minutes_that_wrap() {
 fn full_clock_and_zeroed_clock() {
     assert_eq!(Clock::new(24, 0), Clock::new(0, 0));
 }
+
+//
+// Seconds
+//
+
+#[test]
+fn seconds_only_show_in_the_alternate_form() {
+    let clock = Clock::new_hms(8, 5, 9);
+    assert_eq!(clock.to_string(), "08:05");
+    assert_eq!(format!("{clock:#}"), "08:05:09");
+    assert_eq!(format!("{:#}", Clock::new(8, 5)), "08:05:00");
+}
+
+#[test]
+fn seconds_roll_over() {
+    assert_eq!(Clock::new_hms(0, 0, 61), Clock::new_hms(0, 1, 1));
+    assert_eq!(Clock::new_hms(23, 59, 60), Clock::new(0, 0));
+    assert_eq!(Clock::new_hms(0, 0, -1), Clock::new_hms(23, 59, 59));
+    assert_eq!(Clock::new_hms(1, 0, -3600 * 25), Clock::new(0, 0));
+}
+
+#[test]
+fn seconds_tell_clocks_apart() {
+    assert_ne!(Clock::new_hms(10, 0, 1), Clock::new(10, 0));
+}
+
+#[test]
+fn add_seconds() {
+    let clock = Clock::new_hms(23, 59, 30).add_seconds(45);
+    assert_eq!(format!("{clock:#}"), "00:00:15");
+    assert_eq!(Clock::new(0, 0).add_seconds(-1), Clock::new_hms(23, 59, 59));
+    assert_eq!(
+        Clock::new(0, 0).add_seconds(i32::MAX),
+        Clock::new_hms(0, 0, i32::MAX)
+    );
+}
+
+#[test]
+fn add_minutes_keeps_the_seconds() {
+    let clock = Clock::new_hms(10, 0, 30).add_minutes(-61);
+    assert_eq!(format!("{clock:#}"), "08:59:30");
+}
+
+//
+// Parsing
+//
+
+#[test]
+fn parse_what_display_prints() {
+    for clock in [Clock::new(0, 0), Clock::new(9, 5), Clock::new(23, 59)] {
+        assert_eq!(clock.to_string().parse(), Ok(clock));
+    }
+    let precise = Clock::new_hms(13, 7, 42);
+    assert_eq!(format!("{precise:#}").parse(), Ok(precise));
+}
+
+#[test]
+fn parse_a_single_digit_hour() {
+    assert_eq!("7:30".parse(), Ok(Clock::new(7, 30)));
+}
+
+#[test]
+fn parse_errors_name_the_component() {
+    assert_eq!(
+        "24:00".parse::<Clock>(),
+        Err(ParseClockError::InvalidHours("24".into()))
+    );
+    assert_eq!(
+        "123:00".parse::<Clock>(),
+        Err(ParseClockError::InvalidHours("123".into()))
+    );
+    assert_eq!(
+        "10:5".parse::<Clock>(),
+        Err(ParseClockError::InvalidMinutes("5".into()))
+    );
+    assert_eq!(
+        "10:60".parse::<Clock>(),
+        Err(ParseClockError::InvalidMinutes("60".into()))
+    );
+    assert_eq!(
+        "10:+5".parse::<Clock>(),
+        Err(ParseClockError::InvalidMinutes("+5".into()))
+    );
+    assert_eq!(
+        "10:05:99".parse::<Clock>(),
+        Err(ParseClockError::InvalidSeconds("99".into()))
+    );
+    assert_eq!("1005".parse::<Clock>(), Err(ParseClockError::Format));
+    assert_eq!("10:05:00:00".parse::<Clock>(), Err(ParseClockError::Format));
+    assert_eq!(
+        ":".parse::<Clock>(),
+        Err(ParseClockError::InvalidHours("".into()))
+    );
+}
+
+//
+// Operators
+//
+
+#[test]
+fn add_and_subtract_durations() {
+    use std::time::Duration;
+
+    assert_eq!(
+        Clock::new(10, 0) + Duration::from_secs(600),
+        Clock::new(10, 10)
+    );
+    assert_eq!(
+        Clock::new(0, 5) - Duration::from_secs(600),
+        Clock::new(23, 55)
+    );
+    assert_eq!(
+        Clock::new(10, 0) + Duration::from_millis(1999),
+        Clock::new_hms(10, 0, 1)
+    );
+    let days = Duration::from_secs(86_400 * 10_000 + 60);
+    assert_eq!(Clock::new(10, 0) + days, Clock::new(10, 1));
+    assert_eq!(Clock::new(10, 0) - days, Clock::new(9, 59));
+    assert_eq!(Clock::new(10, 0) + Duration::MAX, Clock::new_hms(17, 0, 15));
+}
+
+#[test]
+fn add_and_subtract_minutes() {
+    assert_eq!(Clock::new(23, 50) + 30, Clock::new(0, 20));
+    assert_eq!(Clock::new(0, 20) - 30, Clock::new(23, 50));
+    assert_eq!(Clock::new(0, 20) - -30, Clock::new(0, 50));
+    assert_eq!(
+        Clock::new(0, 0) - i32::MIN,
+        Clock::new(0, 0).add_minutes(i32::MAX) + 1
+    );
+}
+
+#[test]
+fn assign_operators() {
+    use std::time::Duration;
+
+    let mut clock = Clock::new(12, 0);
+    clock += 15;
+    clock += Duration::from_secs(3600);
+    assert_eq!(clock, Clock::new(13, 15));
+    clock -= 20;
+    clock -= Duration::from_secs(30);
+    assert_eq!(clock, Clock::new_hms(12, 54, 30));
+}
+
+#[test]
+fn add_hours() {
+    assert_eq!(Clock::new(22, 30).add_hours(3), Clock::new(1, 30));
+    assert_eq!(Clock::new(1, 30).add_hours(-3), Clock::new(22, 30));
+    assert_eq!(
+        Clock::new(1, 30).add_hours(i32::MAX),
+        Clock::new(i32::MAX, 0).add_minutes(90)
+    );
+}
+
+//
+// Differences
+//
+
+#[test]
+fn minutes_until_goes_forward() {
+    assert_eq!(Clock::new(9, 0).minutes_until(&Clock::new(10, 30)), 90);
+    assert_eq!(Clock::new(23, 50).minutes_until(&Clock::new(0, 10)), 20);
+    assert_eq!(Clock::new(10, 30).minutes_until(&Clock::new(9, 0)), 1350);
+    assert_eq!(Clock::new(8, 0).minutes_until(&Clock::new(8, 0)), 0);
+    assert_eq!(Clock::new_hms(8, 0, 30).minutes_until(&Clock::new(8, 2)), 1);
+}
+
+#[test]
+fn signed_difference_takes_the_short_way() {
+    assert_eq!(Clock::new(9, 0).signed_difference(&Clock::new(10, 30)), 90);
+    assert_eq!(Clock::new(10, 30).signed_difference(&Clock::new(9, 0)), -90);
+    assert_eq!(
+        Clock::new(0, 10).signed_difference(&Clock::new(23, 50)),
+        -20
+    );
+    assert_eq!(Clock::new(23, 50).signed_difference(&Clock::new(0, 10)), 20);
+    assert_eq!(Clock::new(0, 0).signed_difference(&Clock::new(12, 0)), 720);
+    assert_eq!(Clock::new(12, 0).signed_difference(&Clock::new(0, 0)), 720);
+}
+
+//
+// 12-hour format
+//
+
+#[test]
+fn twelve_hour_format() {
+    assert_eq!(Clock::new(14, 5).format_12h(), "2:05 PM");
+    assert_eq!(Clock::new(9, 30).format_12h(), "9:30 AM");
+    assert_eq!(Clock::new(23, 59).format_12h(), "11:59 PM");
+}
+
+#[test]
+fn twelve_hour_midnight_and_noon() {
+    assert_eq!(Clock::new(0, 0).format_12h(), "12:00 AM");
+    assert_eq!(Clock::new(0, 1).format_12h(), "12:01 AM");
+    assert_eq!(Clock::new(12, 0).format_12h(), "12:00 PM");
+    assert_eq!(Clock::new(11, 59).format_12h(), "11:59 AM");
+}
+
+//
+// Components and conversions
+//
+
+#[test]
+fn components() {
+    let clock = Clock::new_hms(25, 61, 62);
+    assert_eq!((clock.hours(), clock.minutes(), clock.seconds()), (2, 2, 2));
+    assert_eq!(clock.total_minutes(), 122);
+    assert_eq!(Clock::new(23, 59).total_minutes(), 1439);
+}
+
+#[test]
+fn to_and_from_minutes() {
+    assert_eq!(u32::from(Clock::new(10, 30)), 630);
+    assert_eq!(u32::from(Clock::new_hms(0, 1, 59)), 1);
+    assert_eq!(Clock::try_from(630), Ok(Clock::new(10, 30)));
+    assert_eq!(Clock::try_from(1439), Ok(Clock::new(23, 59)));
+    assert_eq!(Clock::try_from(1440), Err(MinutesOutOfRange(1440)));
+    assert_eq!(Clock::try_from(u32::MAX), Err(MinutesOutOfRange(u32::MAX)));
+}
+
+//
+// Interop
+//
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_round_trip() {
+    use chrono::NaiveTime;
+
+    let time = NaiveTime::from_hms_milli_opt(14, 5, 9, 750).unwrap();
+    let clock = Clock::from(time);
+    assert_eq!(clock, Clock::new_hms(14, 5, 9));
+    assert_eq!(
+        NaiveTime::from(clock),
+        NaiveTime::from_hms_opt(14, 5, 9).unwrap()
+    );
+    assert_eq!(NaiveTime::from(Clock::new(24, 0)), NaiveTime::MIN);
+    let long_day = Clock::new(23, 0).with_day_length(26 * 60).add_hours(2);
+    assert_eq!(
+        NaiveTime::from(long_day),
+        NaiveTime::from_hms_opt(1, 0, 0).unwrap()
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_round_trip() {
+    use time::Time;
+
+    let time = Time::from_hms_milli(23, 59, 59, 999).unwrap();
+    let clock = Clock::from(time);
+    assert_eq!(clock, Clock::new_hms(23, 59, 59));
+    assert_eq!(Time::from(clock), Time::from_hms(23, 59, 59).unwrap());
+    assert_eq!(Time::from(Clock::new(0, 0)), Time::MIDNIGHT);
+    let long_day = Clock::new(23, 0).with_day_length(26 * 60).add_hours(2);
+    assert_eq!(Time::from(long_day), Time::from_hms(1, 0, 0).unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_uses_the_display_string() {
+    assert_eq!(
+        serde_json::to_string(&Clock::new(9, 5)).unwrap(),
+        r#""09:05""#
+    );
+    assert_eq!(
+        serde_json::to_string(&Clock::new_hms(9, 5, 30)).unwrap(),
+        r#""09:05:30""#
+    );
+    assert_eq!(
+        serde_json::from_str::<Clock>(r#""9:05""#).unwrap(),
+        Clock::new(9, 5)
+    );
+    assert_eq!(
+        serde_json::from_str::<Clock>(r#""09:05:30""#).unwrap(),
+        Clock::new_hms(9, 5, 30)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_rejects_bad_times() {
+    let error = serde_json::from_str::<Clock>(r#""25:00""#).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains(r#""25" isn't an hour from 0 to 23"#),
+        "{error}"
+    );
+    assert!(serde_json::from_str::<Clock>("545").is_err());
+}
+
+//
+// Ordering
+//
+
+#[test]
+fn clocks_sort_from_midnight() {
+    let mut schedule = vec![
+        Clock::new(13, 0),
+        Clock::new(0, 5),
+        Clock::new(24, 1),
+        Clock::new(9, 30),
+    ];
+    schedule.sort();
+    assert_eq!(
+        schedule,
+        vec![
+            Clock::new(0, 1),
+            Clock::new(0, 5),
+            Clock::new(9, 30),
+            Clock::new(13, 0)
+        ]
+    );
+    assert!(Clock::new_hms(10, 0, 1) > Clock::new(10, 0));
+    assert_eq!(Clock::new(23, 0).max(Clock::new(-2, 0)), Clock::new(23, 0));
+}
+
+#[test]
+fn clocks_as_btreemap_keys() {
+    use std::collections::BTreeMap;
+
+    let timetable = BTreeMap::from([
+        (Clock::new(18, 0), "dinner"),
+        (Clock::new(7, 0), "breakfast"),
+    ]);
+    assert_eq!(
+        timetable.values().copied().collect::<Vec<_>>(),
+        vec!["breakfast", "dinner"]
+    );
+    assert_eq!(
+        timetable.range(Clock::new(12, 0)..).next(),
+        Some((&Clock::new(18, 0), &"dinner"))
+    );
+}
+
+//
+// Ticks
+//
+
+#[test]
+fn ticks_every_fifteen_minutes() {
+    let times: Vec<String> = Clock::new(6, 30)
+        .ticks(15)
+        .take(4)
+        .map(|c| c.to_string())
+        .collect();
+    assert_eq!(times, ["06:30", "06:45", "07:00", "07:15"]);
+}
+
+#[test]
+fn ticks_wrap_past_midnight() {
+    let times: Vec<Clock> = Clock::new(23, 0).ticks(40).take(3).collect();
+    assert_eq!(
+        times,
+        [Clock::new(23, 0), Clock::new(23, 40), Clock::new(0, 20)]
+    );
+    let back: Vec<Clock> = Clock::new(0, 10).ticks(-20).take(2).collect();
+    assert_eq!(back, [Clock::new(0, 10), Clock::new(23, 50)]);
+}
+
+#[test]
+fn ticks_are_not_borrowed() {
+    let ticks = {
+        let start = Clock::new(8, 0);
+        start.ticks(60)
+    };
+    assert_eq!(ticks.take(24).last(), Some(Clock::new(7, 0)));
+}
+
+//
+// Crossing midnight
+//
+
+#[test]
+fn overflowing_add_minutes_counts_midnights() {
+    assert_eq!(
+        Clock::new(23, 50).overflowing_add_minutes(30),
+        (Clock::new(0, 20), 1)
+    );
+    assert_eq!(
+        Clock::new(10, 0).overflowing_add_minutes(30),
+        (Clock::new(10, 30), 0)
+    );
+    assert_eq!(
+        Clock::new(0, 10).overflowing_add_minutes(-20),
+        (Clock::new(23, 50), -1)
+    );
+    assert_eq!(
+        Clock::new(0, 0).overflowing_add_minutes(3 * 1440),
+        (Clock::new(0, 0), 3)
+    );
+}
+
+#[test]
+fn dated_clock_moves_on_a_day() {
+    let late = DatedClock::new(0, Clock::new(23, 50));
+    let later = late.add_minutes(30);
+    assert_eq!(later.day(), 1);
+    assert_eq!(later.clock(), Clock::new(0, 20));
+    assert_eq!(later.to_string(), "day 1, 00:20");
+    assert!(later > late);
+    assert_eq!(later.add_minutes(-30), late);
+}
+
+#[test]
+fn dated_clock_alternate_display_shows_seconds() {
+    let clock = DatedClock::new(2, Clock::new_hms(6, 0, 5));
+    assert_eq!(format!("{clock:#}"), "day 2, 06:00:05");
+}
+
+//
+// Offsets
+//
+
+#[test]
+fn shift_by_offsets() {
+    let noon = Clock::new(12, 0);
+    assert_eq!(noon.with_offset(Offset::new(5, 30)), Clock::new(17, 30));
+    assert_eq!(noon.with_offset(Offset::new(-3, -30)), Clock::new(8, 30));
+    assert_eq!(noon.with_offset(-90), Clock::new(10, 30));
+    assert_eq!(noon.with_offset(Offset::UTC), noon);
+    assert_eq!(
+        Clock::new(22, 0).with_offset(Offset::new(9, 0)),
+        Clock::new(7, 0)
+    );
+}
+
+#[test]
+fn convert_between_zones() {
+    let new_york = Offset::new(-5, 0);
+    let kolkata = Offset::new(5, 30);
+    assert_eq!(
+        Clock::new(21, 0).convert(new_york, kolkata),
+        Clock::new(7, 30)
+    );
+    assert_eq!(
+        Clock::new(7, 30).convert(kolkata, new_york),
+        Clock::new(21, 0)
+    );
+}
+
+#[test]
+fn offset_display() {
+    assert_eq!(Offset::new(5, 30).to_string(), "+05:30");
+    assert_eq!(Offset::new(-3, -30).to_string(), "-03:30");
+    assert_eq!(Offset::from(-45).to_string(), "-00:45");
+    assert_eq!(Offset::UTC.to_string(), "+00:00");
+    assert_eq!(Offset::new(-9, 0).minutes(), -540);
+}
+
+//
+// Constants
+//
+
+const OPENING: Clock = Clock::new(9, 0).add_minutes(30);
+
+static SHIFTS: [Clock; 3] = [clock!(06:00), clock!(14:00), clock!(22:00:30)];
+
+#[test]
+fn clocks_in_constants() {
+    assert_eq!(OPENING, Clock::new(9, 30));
+    assert_eq!(SHIFTS[1], Clock::new(14, 0));
+    assert_eq!(SHIFTS[2], Clock::new_hms(22, 0, 30));
+    assert_eq!(clock!(9:05).to_string(), "09:05");
+    const { assert!(Clock::new(25, 0).hours() == 1) };
+}
+
+//
+// Alarms
+//
+
+#[test]
+fn one_off_alarm() {
+    let alarm = Alarm::at(Clock::new(7, 0));
+    assert_eq!(alarm.next_after(Clock::new(6, 0)), Clock::new(7, 0));
+    assert_eq!(alarm.next_after(Clock::new(7, 0)), Clock::new(7, 0));
+    assert_eq!(alarm.next_after(Clock::new(23, 0)), Clock::new(7, 0));
+}
+
+#[test]
+fn recurring_alarm_runs_until_midnight() {
+    let alarm = Alarm::recurring(Clock::new(6, 30), 15);
+    assert_eq!(alarm.next_after(Clock::new(6, 30)), Clock::new(6, 45));
+    assert_eq!(alarm.next_after(Clock::new(6, 44)), Clock::new(6, 45));
+    assert_eq!(alarm.next_after(Clock::new(12, 0)), Clock::new(12, 15));
+    assert_eq!(alarm.next_after(Clock::new(23, 45)), Clock::new(6, 30));
+    assert_eq!(alarm.next_after(Clock::new(2, 0)), Clock::new(6, 30));
+    let odd = Alarm::recurring(Clock::new(22, 0), 70);
+    assert_eq!(odd.next_after(Clock::new(22, 30)), Clock::new(23, 10));
+    assert_eq!(odd.next_after(Clock::new(23, 10)), Clock::new(22, 0));
+}
+
+#[test]
+#[should_panic]
+fn alarms_must_recur_forward() {
+    Alarm::recurring(Clock::new(0, 0), 0);
+}
+
+#[test]
+fn schedule_next_after_wraps() {
+    let schedule = Schedule::new()
+        .alarm(Alarm::at(Clock::new(7, 0)))
+        .alarm(Alarm::recurring(Clock::new(20, 0), 120));
+    assert_eq!(
+        schedule.next_after(Clock::new(6, 0)),
+        Some(Clock::new(7, 0))
+    );
+    assert_eq!(
+        schedule.next_after(Clock::new(21, 0)),
+        Some(Clock::new(22, 0))
+    );
+    assert_eq!(
+        schedule.next_after(Clock::new(22, 0)),
+        Some(Clock::new(7, 0))
+    );
+    assert_eq!(Schedule::new().next_after(Clock::new(0, 0)), None);
+}
+
+#[test]
+fn schedule_due_between_crosses_midnight() {
+    let mut schedule = Schedule::new();
+    schedule.add(Alarm::at(Clock::new(7, 0)));
+    schedule.add(Alarm::recurring(Clock::new(22, 0), 60));
+    schedule.add(Alarm::at(Clock::new(23, 0)));
+    assert_eq!(
+        schedule.due_between(Clock::new(21, 30), Clock::new(7, 0)),
+        [Clock::new(22, 0), Clock::new(23, 0), Clock::new(7, 0)]
+    );
+    assert_eq!(
+        schedule.due_between(Clock::new(22, 0), Clock::new(22, 59)),
+        []
+    );
+    assert_eq!(schedule.due_between(Clock::new(8, 0), Clock::new(8, 0)), []);
+    assert_eq!(
+        schedule.due_between(Clock::new(0, 0), Clock::new(23, 59)),
+        [Clock::new(7, 0), Clock::new(22, 0), Clock::new(23, 0)]
+    );
+}
+
+//
+// Now
+//
+
+#[cfg(feature = "std-time")]
+#[test]
+fn now_from_a_fake_source() {
+    assert_eq!(Clock::now_from(&Clock::new(9, 15)), Clock::new(9, 15));
+
+    let ticks = std::cell::Cell::new(0);
+    let fake = || {
+        ticks.set(ticks.get() + 1);
+        Clock::new(12, ticks.get())
+    };
+    assert_eq!(Clock::now_from(&fake), Clock::new(12, 1));
+    assert_eq!(Clock::now_from(&fake), Clock::new(12, 2));
+}
+
+#[cfg(feature = "std-time")]
+#[test]
+fn now_from_the_system() {
+    let utc = Clock::now(Offset::UTC);
+    let ahead = Clock::now_from(&SystemClock {
+        offset: Offset::new(5, 30),
+    });
+    let behind = Clock::now(-240);
+    // Any time passing between the readings is well under a minute.
+    assert_eq!(utc.signed_difference(&ahead), 330);
+    assert_eq!(utc.signed_difference(&behind), -240);
+}
+
+//
+// Custom formats
+//
+
+#[test]
+fn format_patterns() {
+    let afternoon = Clock::new_hms(14, 5, 9);
+    assert_eq!(afternoon.format("%H:%M"), "14:05");
+    assert_eq!(afternoon.format("%I:%M %p"), "02:05 PM");
+    assert_eq!(afternoon.format("%H:%M:%S"), "14:05:09");
+    assert_eq!(Clock::new(9, 30).format("%k:%M"), " 9:30");
+    assert_eq!(Clock::new(0, 0).format("%I %p"), "12 AM");
+    assert_eq!(Clock::new(12, 0).format("%I %p"), "12 PM");
+}
+
+#[test]
+fn format_passes_other_text_through() {
+    let clock = Clock::new(8, 0);
+    assert_eq!(clock.format("at %Hh, 100%% sure"), "at 08h, 100% sure");
+    assert_eq!(clock.format("%Q and %"), "%Q and %");
+    assert_eq!(clock.format(""), "");
+}
+
+//
+// Rounding
+//
+
+#[test]
+fn round_to_a_grid() {
+    let clock = Clock::new(10, 7);
+    assert_eq!(clock.round_to(15, RoundMode::Down), Clock::new(10, 0));
+    assert_eq!(clock.round_to(15, RoundMode::Up), Clock::new(10, 15));
+    assert_eq!(clock.round_to(15, RoundMode::Nearest), Clock::new(10, 0));
+    assert_eq!(
+        Clock::new(10, 8).round_to(15, RoundMode::Nearest),
+        Clock::new(10, 15)
+    );
+    assert_eq!(
+        Clock::new(10, 15).round_to(5, RoundMode::Nearest),
+        Clock::new(10, 15)
+    );
+    assert_eq!(
+        Clock::new(10, 30).round_to(15, RoundMode::Up),
+        Clock::new(10, 30)
+    );
+}
+
+#[test]
+fn round_halfway_goes_up() {
+    assert_eq!(
+        Clock::new(10, 15).round_to(30, RoundMode::Nearest),
+        Clock::new(10, 30)
+    );
+    assert_eq!(
+        Clock::new_hms(10, 2, 29).round_to(5, RoundMode::Nearest),
+        Clock::new(10, 0)
+    );
+    assert_eq!(
+        Clock::new_hms(10, 2, 30).round_to(5, RoundMode::Nearest),
+        Clock::new(10, 5)
+    );
+}
+
+#[test]
+fn round_wraps_at_midnight() {
+    assert_eq!(
+        Clock::new(23, 58).round_to(15, RoundMode::Up),
+        Clock::new(0, 0)
+    );
+    assert_eq!(
+        Clock::new(23, 58).round_to(5, RoundMode::Nearest),
+        Clock::new(0, 0)
+    );
+    assert_eq!(
+        Clock::new(0, 2).round_to(5, RoundMode::Down),
+        Clock::new(0, 0)
+    );
+    assert_eq!(
+        Clock::new_hms(10, 0, 1).round_to(1, RoundMode::Up),
+        Clock::new(10, 1)
+    );
+}
+
+#[test]
+fn round_up_lands_on_midnight_in_an_uneven_day() {
+    // A 100-minute day has steps of 40 at 0:00, 0:40 and 1:20.
+    let short_day = |h, m| Clock::new(h, m).with_day_length(100);
+    assert_eq!(
+        short_day(1, 21).round_to(40, RoundMode::Up),
+        short_day(0, 0)
+    );
+    assert_eq!(
+        short_day(1, 39).round_to(40, RoundMode::Nearest),
+        short_day(1, 20)
+    );
+    assert_eq!(
+        short_day(1, 39).round_to(40, RoundMode::Down),
+        short_day(1, 20)
+    );
+}
+
+#[test]
+#[should_panic]
+fn round_to_nothing() {
+    Clock::new(10, 0).round_to(0, RoundMode::Nearest);
+}
+
+//
+// Ranges
+//
+
+#[test]
+fn is_between_within_the_day() {
+    let (nine, five) = (Clock::new(9, 0), Clock::new(17, 0));
+    assert!(Clock::new(12, 0).is_between(nine, five));
+    assert!(nine.is_between(nine, five));
+    assert!(!five.is_between(nine, five));
+    assert!(!Clock::new(20, 0).is_between(nine, five));
+}
+
+#[test]
+fn is_between_across_midnight() {
+    let (night, morning) = (Clock::new(22, 0), Clock::new(6, 0));
+    assert!(Clock::new(23, 30).is_between(night, morning));
+    assert!(Clock::new(0, 0).is_between(night, morning));
+    assert!(Clock::new(5, 59).is_between(night, morning));
+    assert!(!Clock::new(6, 0).is_between(night, morning));
+    assert!(!Clock::new(12, 0).is_between(night, morning));
+}
+
+#[test]
+fn clock_ranges() {
+    let night = ClockRange::new(Clock::new(22, 0), Clock::new(6, 0));
+    assert_eq!(night.duration_minutes(), 480);
+    assert!(night.contains(Clock::new(3, 0)));
+
+    let empty = ClockRange::new(Clock::new(8, 0), Clock::new(8, 0));
+    assert_eq!(empty.duration_minutes(), 0);
+    assert!(!empty.contains(Clock::new(8, 0)));
+    assert!(!empty.contains(Clock::new(20, 0)));
+}
+
+//
+// Day length
+//
+
+#[test]
+fn shorter_days_wrap_sooner() {
+    let shift = Clock::new(8, 0).with_day_length(600);
+    assert_eq!(shift.day_length(), 600);
+    assert_eq!(shift.add_hours(3).to_string(), "01:00");
+    assert_eq!(shift.add_minutes(-9 * 60).to_string(), "09:00");
+    assert_eq!(Clock::new(12, 0).with_day_length(600).to_string(), "02:00");
+}
+
+#[test]
+fn longer_days_go_past_24_hours() {
+    // A Mars sol, to the minute.
+    let sol = Clock::new(23, 0).with_day_length(24 * 60 + 40);
+    assert_eq!(sol.add_minutes(90).to_string(), "24:30");
+    assert_eq!(sol.add_minutes(100).to_string(), "00:00");
+    assert_eq!(sol.overflowing_add_minutes(100).1, 1);
+}
+
+#[test]
+fn day_length_is_part_of_the_clock() {
+    assert_eq!(Clock::new(0, 0).day_length(), 1440);
+    assert_ne!(Clock::new(1, 0), Clock::new(1, 0).with_day_length(600));
+    assert_eq!(
+        Clock::new(1, 0).with_day_length(600).with_day_length(1440),
+        Clock::new(1, 0)
+    );
+}
+
+#[test]
+fn differences_use_the_day_length() {
+    let start = Clock::new(9, 0).with_day_length(600);
+    let end = Clock::new(1, 0).with_day_length(600);
+    assert_eq!(start.minutes_until(&end), 120);
+    assert_eq!(end.signed_difference(&start), -120);
+    assert_eq!(
+        start + std::time::Duration::from_secs(600 * 60 + 60),
+        start.add_minutes(1)
+    );
+}
+
+#[test]
+#[should_panic]
+fn days_have_to_have_some_length() {
+    Clock::new(0, 0).with_day_length(0);
+}
+
+//
+// Stopwatch
+//
+
+#[test]
+fn stopwatch_elapsed() {
+    use std::time::Duration;
+
+    let mut watch = Stopwatch::start_at(Clock::new(9, 0));
+    assert_eq!(watch.elapsed_at(Clock::new(9, 0)), Duration::ZERO);
+    assert_eq!(
+        watch.elapsed_at(Clock::new_hms(9, 1, 30)),
+        Duration::from_secs(90)
+    );
+    assert_eq!(
+        watch.elapsed_at(Clock::new(10, 0)),
+        Duration::from_secs(3600)
+    );
+    assert_eq!(watch.started(), Clock::new(9, 0));
+}
+
+#[test]
+fn stopwatch_across_midnight() {
+    use std::time::Duration;
+
+    let mut watch = Stopwatch::start_at(Clock::new(22, 0));
+    assert_eq!(
+        watch.elapsed_at(Clock::new(1, 0)),
+        Duration::from_secs(3 * 3600)
+    );
+    // Read often enough, it keeps going past a whole day.
+    watch.elapsed_at(Clock::new(12, 0));
+    assert_eq!(
+        watch.elapsed_at(Clock::new(23, 0)),
+        Duration::from_secs(25 * 3600)
+    );
+}
+
+#[test]
+fn stopwatch_laps() {
+    use std::time::Duration;
+
+    let mut watch = Stopwatch::start_at(Clock::new(23, 50));
+    assert_eq!(watch.lap(Clock::new(23, 55)), Duration::from_secs(300));
+    watch.elapsed_at(Clock::new(0, 0));
+    assert_eq!(watch.lap(Clock::new(0, 10)), Duration::from_secs(900));
+    assert_eq!(
+        watch.laps(),
+        [Duration::from_secs(300), Duration::from_secs(900)]
+    );
+    assert_eq!(
+        watch.elapsed_at(Clock::new(0, 10)),
+        Duration::from_secs(1200)
+    );
+}
@@ -1,46 +1,220 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
 
+/// A code that has been checked with the Luhn algorithm.
+///
+/// `Luhn::from` takes the string types (`&str`, `String`, `&String`,
+/// `Box<str>`, `Cow<str>` and `char`) and the primitive integers, which are
+/// checked arithmetically rather than formatted into a `String` first.
+///
+/// This is a breaking change from the old blanket `From<T: ToString>`. That
+/// impl overlaps any impl for a specific type, and stable Rust can't pick
+/// the more specific one, so it had to go for the integers to get their own
+/// fast path. Anything else that used to work through `ToString` needs
+/// converting first:
+///
+/// ```
+/// use luhn_from::Luhn;
+///
+/// struct Sin(u32);
+///
+/// impl std::fmt::Display for Sin {
+///     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+///         write!(f, "{:03} {:03} {:03}", self.0 / 1_000_000, self.0 / 1000 % 1000, self.0 % 1000)
+///     }
+/// }
+///
+/// assert!(Luhn::from(Sin(46_454_286).to_string()).is_valid());
+/// ```
 #[derive(Debug)]
 pub struct Luhn {
     is_valid: bool,
 }
 
-/// The numeric types in the tests all implement ToString
-impl<T: ToString> From<T> for Luhn {
-    fn from(input: T) -> Self {
+/// Why a string couldn't be read as a Luhn code at all, as opposed to
+/// reading fine and failing the checksum.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LuhnParseError {
+    /// Something other than a digit or whitespace, at byte offset `index`.
+    InvalidCharacter { ch: char, index: usize },
+    /// Fewer than two digits; `len` is how many there were.
+    TooShort { len: usize },
+}
+
+impl fmt::Display for LuhnParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LuhnParseError::InvalidCharacter { ch, index } => {
+                write!(f, "unexpected character {ch:?} at position {index}")
+            }
+            LuhnParseError::TooShort { len } => {
+                write!(f, "need at least 2 digits, got {len}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LuhnParseError {}
+
+/// The strict way in: malformed input is an error, and `Ok` means the code
+/// was well formed, whether or not its checksum matches. `Luhn::from` is the
+/// lenient way, folding both into `is_valid() == false`.
+///
+/// This is `FromStr` rather than `TryFrom<&str>` because `From<&str>` already
+/// gives `Luhn` an infallible `TryFrom<&str>` through the standard library's
+/// blanket impl, and the two can't coexist. The same goes for `u64`; besides,
+/// every number is well formed, so the only thing `TryFrom<u64>` could reject
+/// is a single digit.
+///
+/// ```
+/// use luhn_from::{Luhn, LuhnParseError};
+///
+/// assert!("046 454 286".parse::<Luhn>().unwrap().is_valid());
+/// assert!(!"046 454 287".parse::<Luhn>().unwrap().is_valid());
+/// assert_eq!(
+///     "046a 454 286".parse::<Luhn>().unwrap_err(),
+///     LuhnParseError::InvalidCharacter { ch: 'a', index: 3 },
+/// );
+/// ```
+impl FromStr for Luhn {
+    type Err = LuhnParseError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let digits = digits(code)?;
+        Ok(Self {
+            is_valid: checksum_matches(&digits),
+        })
+    }
+}
+
+/// Strings go digit by digit, skipping whitespace.
+impl From<&str> for Luhn {
+    fn from(input: &str) -> Self {
         Self {
-            is_valid: is_valid_luhn(&input.to_string()),
+            is_valid: is_valid_luhn(input),
         }
     }
 }
 
+impl From<String> for Luhn {
+    fn from(input: String) -> Self {
+        Self::from(input.as_str())
+    }
+}
+
+impl From<&String> for Luhn {
+    fn from(input: &String) -> Self {
+        Self::from(input.as_str())
+    }
+}
+
+impl From<Box<str>> for Luhn {
+    fn from(input: Box<str>) -> Self {
+        Self::from(&*input)
+    }
+}
+
+impl From<Cow<'_, str>> for Luhn {
+    fn from(input: Cow<'_, str>) -> Self {
+        Self::from(&*input)
+    }
+}
+
+/// One character is at most one digit, which is never enough.
+impl From<char> for Luhn {
+    fn from(_: char) -> Self {
+        Self { is_valid: false }
+    }
+}
+
+/// Integers are checked arithmetically, with no formatting into a `String`.
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Luhn {
+                fn from(input: $t) -> Self {
+                    Self {
+                        is_valid: is_valid_luhn_number(input as u128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// A minus sign isn't a digit, so negative numbers are never valid.
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Luhn {
+                fn from(input: $t) -> Self {
+                    Self {
+                        is_valid: input >= 0 && is_valid_luhn_number(input as u128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
 impl Luhn {
     pub fn is_valid(&self) -> bool {
         self.is_valid
     }
 }
 
-
-/// We're private and we're free
+/// Each digit doubled, less 9 when that takes it past 9.
 static DOUBLED: [u32; 10] = [0, 2, 4, 6, 8, 1, 3, 5, 7, 9];
 
 fn is_valid_luhn(code: &str) -> bool {
-    let Some(digits) = code
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .map(|c| c.to_digit(10))
-        .collect::<Option<Vec<_>>>()
-    else {
+    digits(code).is_ok_and(|digits| checksum_matches(&digits))
+}
+
+/// The digits of `code`, skipping whitespace, as long as there are at least
+/// two and nothing else.
+fn digits(code: &str) -> Result<Vec<u32>, LuhnParseError> {
+    let digits = code
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(index, ch)| ch.to_digit(10).ok_or(LuhnParseError::InvalidCharacter { ch, index }))
+        .collect::<Result<Vec<_>, _>>()?;
+    if digits.len() < 2 {
+        return Err(LuhnParseError::TooShort { len: digits.len() });
+    }
+    Ok(digits)
+}
+
+fn checksum_matches(digits: &[u32]) -> bool {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &dig)| {
+            if i % 2 == 1 { DOUBLED[dig as usize] } else { dig }
+        })
+        .sum::<u32>()
+        .is_multiple_of(10)
+}
+
+fn is_valid_luhn_number(mut n: u128) -> bool {
+    // A single digit is never valid, same as for strings.
+    if n < 10 {
         return false;
-    };
+    }
 
-    digits.len() > 1
-        && digits
-            .iter()
-            .rev()
-            .enumerate()
-            .map(|(i, &dig)| {
-                if i % 2 == 1 { DOUBLED[dig as usize] } else { dig }
-            })
-            .sum::<u32>()
-            .is_multiple_of(10)
+    // Digits come off the bottom with `% 10`, which is the order Luhn wants.
+    let mut sum = 0;
+    let mut i = 0;
+    while n > 0 {
+        let dig = (n % 10) as u32;
+        sum += if i % 2 == 1 { DOUBLED[dig as usize] } else { dig };
+        n /= 10;
+        i += 1;
+    }
+    sum.is_multiple_of(10)
 }
@@ -90,3 +90,56 @@ fn strings_that_contain_non_digits_are_invalid() {
 fn input_digit_9_is_still_correctly_converted_to_output_digit_9() {
     assert!(Luhn::from("091").is_valid());
 }
+
+#[test]
+fn u128_numbers_are_checked_without_overflow() {
+    assert!(Luhn::from(340_282_366_920_938_463_463_374_607_431_768_211_450u128).is_valid());
+    assert!(Luhn::from(79_927_398_713u128).is_valid());
+    assert!(!Luhn::from(79_927_398_714u128).is_valid());
+}
+
+#[test]
+fn borrowed_string_is_valid() {
+    let code = String::from("046 454 286");
+    assert!(Luhn::from(&code).is_valid());
+}
+
+#[test]
+fn other_string_types_still_convert() {
+    let boxed: Box<str> = "046 454 286".into();
+    assert!(Luhn::from(boxed).is_valid());
+    assert!(Luhn::from(std::borrow::Cow::Borrowed("046 454 286")).is_valid());
+    assert!(!Luhn::from(std::borrow::Cow::<str>::Owned("046 454 287".into())).is_valid());
+    assert!(!Luhn::from('0').is_valid());
+}
+
+#[test]
+fn signed_numbers_follow_their_magnitude_when_positive() {
+    assert!(Luhn::from(46_454_286i32).is_valid());
+    assert!(!Luhn::from(-46_454_286i32).is_valid());
+    assert!(!Luhn::from(0i64).is_valid());
+}
+
+#[test]
+fn parse_tells_bad_characters_from_bad_checksums() {
+    assert!(!"046 454 287".parse::<Luhn>().unwrap().is_valid());
+    assert_eq!(
+        "046a 454 286".parse::<Luhn>().unwrap_err(),
+        LuhnParseError::InvalidCharacter { ch: 'a', index: 3 }
+    );
+}
+
+#[test]
+fn parse_rejects_single_digit() {
+    assert_eq!(
+        " 0 ".parse::<Luhn>().unwrap_err(),
+        LuhnParseError::TooShort { len: 1 }
+    );
+}
+
+#[test]
+fn parse_accepts_what_from_accepts() {
+    for code in ["059", "091", "046 454 286", "8273 1232 7352 0569"] {
+        assert_eq!(code.parse::<Luhn>().unwrap().is_valid(), Luhn::from(code).is_valid());
+    }
+}
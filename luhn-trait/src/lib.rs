@@ -0,0 +1,85 @@
+/// Anything that can be checked against the Luhn formula.
+pub trait Luhn {
+    fn valid_luhn(&self) -> bool;
+}
+
+/// Strings go digit by digit, skipping whitespace like the original exercise.
+impl Luhn for str {
+    fn valid_luhn(&self) -> bool {
+        is_valid_luhn(self)
+    }
+}
+
+impl Luhn for String {
+    fn valid_luhn(&self) -> bool {
+        is_valid_luhn(self)
+    }
+}
+
+/// Integers never leave the registers: digits come off the bottom with `% 10`,
+/// which is conveniently the order Luhn wants them in.
+macro_rules! impl_luhn_for_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Luhn for $t {
+                fn valid_luhn(&self) -> bool {
+                    is_valid_luhn_number(u128::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_luhn_for_unsigned!(u8, u16, u32, u64, u128);
+
+impl Luhn for usize {
+    fn valid_luhn(&self) -> bool {
+        // usize is at most 64 bits on every platform Rust supports.
+        is_valid_luhn_number(*self as u128)
+    }
+}
+
+/// Each digit doubled, less 9 when that takes it past 9.
+static DOUBLED: [u32; 10] = [0, 2, 4, 6, 8, 1, 3, 5, 7, 9];
+
+/// What [`Luhn::valid_luhn`] says for strings and `String`s.
+fn is_valid_luhn(code: &str) -> bool {
+    let Some(digits) = code
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(10))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+
+    digits.len() > 1
+        && digits
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &dig)| {
+                if i % 2 == 1 { DOUBLED[dig as usize] } else { dig }
+            })
+            .sum::<u32>()
+            .is_multiple_of(10)
+}
+
+/// What [`Luhn::valid_luhn`] says for every unsigned integer, widened to
+/// `u128` first.
+fn is_valid_luhn_number(mut n: u128) -> bool {
+    // A single digit is never valid, same as for strings.
+    if n < 10 {
+        return false;
+    }
+
+    let mut sum = 0;
+    let mut i = 0;
+    while n > 0 {
+        let dig = (n % 10) as u32;
+        sum += if i % 2 == 1 { DOUBLED[dig as usize] } else { dig };
+        n /= 10;
+        i += 1;
+    }
+    sum.is_multiple_of(10)
+}
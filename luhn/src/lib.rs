@@ -1,24 +1,985 @@
-static DOUBLED: [u32; 10] = [0, 2, 4, 6, 8, 1, 3, 5, 7, 9];
+use std::fmt;
+
+const DOUBLED: [u32; 10] = [0, 2, 4, 6, 8, 1, 3, 5, 7, 9];
+
+/// Why a code failed the Luhn check.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LuhnError {
+    /// Something other than a digit or whitespace, at byte offset `index`.
+    InvalidCharacter { ch: char, index: usize },
+    /// Fewer than two digits; `len` is how many there were.
+    TooShort { len: usize },
+    /// More or fewer digits than [`ValidationOptions`] allow.
+    LengthOutOfRange { len: usize, min: usize, max: usize },
+    /// Every character was fine but the Luhn sum isn't a multiple of the
+    /// base: ten, unless using [`LuhnModN`].
+    ChecksumMismatch { sum: u64 },
+}
+
+impl fmt::Display for LuhnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LuhnError::InvalidCharacter { ch, index } => {
+                write!(f, "unexpected character {ch:?} at position {index}")
+            }
+            LuhnError::TooShort { len } => {
+                write!(f, "need at least 2 digits, got {len}")
+            }
+            LuhnError::LengthOutOfRange { len, min, max } => {
+                write!(f, "need between {min} and {max} digits, got {len}")
+            }
+            LuhnError::ChecksumMismatch { sum } => {
+                write!(f, "checksum {sum} does not check out")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LuhnError {}
+
+/// Running Luhn sums over digits read left to right, in any base.
+///
+/// Which digits get doubled depends on where the number *ends*, which we
+/// don't know until we get there. So keep both answers: `plain` is the sum if
+/// the last digit seen is the check digit, `doubled` is the sum if it's the
+/// one just before. Each new digit shifts everything one place left, so the
+/// two simply swap roles.
+#[derive(Debug, Clone, Copy)]
+struct Sums {
+    base: u32,
+    plain: u64,
+    doubled: u64,
+    count: usize,
+}
+
+impl Sums {
+    const fn new(base: u32) -> Self {
+        Self {
+            base,
+            plain: 0,
+            doubled: 0,
+            count: 0,
+        }
+    }
+
+    const fn push(self, dig: u32) -> Self {
+        // Doubling can carry into a second digit; Luhn adds the two back
+        // together. Base 10 is common enough to get a lookup table.
+        let twice = if self.base == 10 {
+            DOUBLED[dig as usize]
+        } else {
+            let twice = 2 * dig;
+            twice / self.base + twice % self.base
+        };
+        Self {
+            plain: self.doubled + dig as u64,
+            doubled: self.plain + twice as u64,
+            count: self.count + 1,
+            base: self.base,
+        }
+    }
+
+    /// Fold in `chars` read as digits by `to_digit`, skipping whitespace and
+    /// stopping at anything else.
+    fn of(
+        chars: impl IntoIterator<Item = char>,
+        base: u32,
+        to_digit: impl Fn(char) -> Option<u32>,
+    ) -> Result<Self, LuhnError> {
+        Self::new(base).extend(chars, to_digit)
+    }
+
+    /// [`of`](Self::of), carrying on from digits already seen.
+    fn extend(
+        self,
+        chars: impl IntoIterator<Item = char>,
+        to_digit: impl Fn(char) -> Option<u32>,
+    ) -> Result<Self, LuhnError> {
+        self.extend_with(chars, char::is_whitespace, to_digit)
+    }
+
+    /// [`extend`](Self::extend), skipping `is_separator` instead of whitespace.
+    fn extend_with(
+        mut self,
+        chars: impl IntoIterator<Item = char>,
+        is_separator: impl Fn(char) -> bool,
+        to_digit: impl Fn(char) -> Option<u32>,
+    ) -> Result<Self, LuhnError> {
+        let mut index = 0;
+        for ch in chars {
+            if !is_separator(ch) {
+                let dig = to_digit(ch).ok_or(LuhnError::InvalidCharacter { ch, index })?;
+                self = self.push(dig);
+            }
+            index += ch.len_utf8();
+        }
+        Ok(self)
+    }
+
+    fn decimal(chars: impl IntoIterator<Item = char>) -> Result<Self, LuhnError> {
+        Self::of(chars, 10, |c| c.to_digit(10))
+    }
+
+    const fn check(self) -> Result<(), LuhnError> {
+        if self.count < 2 {
+            Err(LuhnError::TooShort { len: self.count })
+        } else if !self.plain.is_multiple_of(self.base as u64) {
+            Err(LuhnError::ChecksumMismatch { sum: self.plain })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The digit to append so that the sums check out. Once it's on the
+    /// end, today's `doubled` is the real sum.
+    fn check_digit(self) -> u32 {
+        let base = u64::from(self.base);
+        ((base - self.doubled % base) % base) as u32
+    }
+}
 
 /// Check a Luhn checksum.
 pub fn is_valid(code: &str) -> bool {
-    let Some(digits) = code
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .map(|c| c.to_digit(10))
-        .collect::<Option<Vec<_>>>()
-    else {
-        return false;
+    // Almost every code is plain ASCII, and bytes are much cheaper to walk
+    // than chars. Anything else may have Unicode whitespace worth skipping.
+    if code.is_ascii() {
+        is_valid_bytes(code.as_bytes())
+    } else {
+        is_valid_chars(code.chars())
+    }
+}
+
+/// Check a Luhn checksum in a single pass over `chars`, without collecting
+/// them anywhere first.
+pub fn is_valid_chars(chars: impl IntoIterator<Item = char>) -> bool {
+    Sums::decimal(chars).and_then(Sums::check).is_ok()
+}
+
+/// Check a Luhn checksum on ASCII bytes. Only ASCII whitespace is skipped,
+/// counting vertical tab the way [`char::is_whitespace`] does; any other
+/// byte, including the start of a multi-byte character, makes the code
+/// invalid.
+///
+/// This is a `const fn`, so fixed identifiers can be checked at compile time:
+///
+/// ```
+/// const _: () = assert!(luhn::is_valid_bytes(b"4539 3195 0343 6467"));
+/// ```
+pub const fn is_valid_bytes(code: &[u8]) -> bool {
+    let mut sums = Sums::new(10);
+    let mut i = 0;
+    while i < code.len() {
+        let byte = code[i];
+        if byte.is_ascii_digit() {
+            sums = sums.push((byte - b'0') as u32);
+        } else if !(byte.is_ascii_whitespace() || byte == b'\x0B') {
+            return false;
+        }
+        i += 1;
+    }
+    sums.check().is_ok()
+}
+
+/// A random Luhn-valid number of `len` digits starting with `prefix`, e.g.
+/// an issuer prefix, for test fixtures and demo data.
+///
+/// Panics if `prefix` has anything besides ASCII digits, or if it leaves no
+/// room for a check digit; `len` has to be at least 2 as well, since a single
+/// digit is never valid.
+#[cfg(feature = "rand")]
+pub fn generate(prefix: &str, len: usize, rng: &mut impl rand::Rng) -> String {
+    assert!(
+        prefix.bytes().all(|byte| byte.is_ascii_digit()),
+        "prefix {prefix:?} must be all digits"
+    );
+    assert!(
+        len >= 2 && prefix.len() < len,
+        "no room for a check digit after {prefix:?} in {len} digits"
+    );
+    let mut code = String::with_capacity(len);
+    code.push_str(prefix);
+    while code.len() < len - 1 {
+        code.push(char::from(b'0' + rng.random_range(0..10)));
+    }
+    let check = checksum_digit(&code).expect("at least one digit");
+    code.extend(char::from_digit(check, 10));
+    code
+}
+
+/// [`is_valid`] for each of `codes`, in order.
+#[cfg(not(feature = "rayon"))]
+pub fn validate_batch<'a>(codes: impl IntoIterator<Item = &'a str>) -> Vec<bool> {
+    codes.into_iter().map(is_valid).collect()
+}
+
+/// [`is_valid`] for each of `codes`, in order, spread across rayon's
+/// thread pool.
+#[cfg(feature = "rayon")]
+pub fn validate_batch<'a>(codes: impl IntoIterator<Item = &'a str>) -> Vec<bool> {
+    use rayon::prelude::*;
+
+    let codes: Vec<&str> = codes.into_iter().collect();
+    codes.par_iter().map(|code| is_valid(code)).collect()
+}
+
+/// Like [`is_valid`], but says what was wrong.
+pub fn validate(code: &str) -> Result<(), LuhnError> {
+    Sums::decimal(code.chars())?.check()
+}
+
+/// The digit that, appended to `partial`, makes it pass the Luhn check.
+/// `None` if `partial` has no digits or anything besides digits and whitespace.
+pub fn checksum_digit(partial: &str) -> Option<u32> {
+    let sums = Sums::decimal(partial.chars())
+        .ok()
+        .filter(|sums| sums.count > 0)?;
+    Some(sums.check_digit())
+}
+
+/// `partial` with its check digit appended.
+pub fn append_check_digit(partial: &str) -> Option<String> {
+    let check = checksum_digit(partial)?;
+    let mut code = partial.to_string();
+    code.extend(char::from_digit(check, 10));
+    Some(code)
+}
+
+/// Every way to make an invalid `code` valid by changing one digit, for
+/// flagging likely typos. Separators stay where they are. Empty if `code` is
+/// already valid or has anything besides digits and whitespace.
+///
+/// ```
+/// let fixes = luhn::suggest_corrections("055 444 286");
+/// assert!(fixes.contains(&"055 444 285".to_string()));
+/// ```
+pub fn suggest_corrections(code: &str) -> Vec<String> {
+    corrections(code, false)
+}
+
+/// Like [`suggest_corrections`], also trying every swap of two neighbouring
+/// digits, the other common slip when typing numbers in.
+pub fn suggest_corrections_with_swaps(code: &str) -> Vec<String> {
+    corrections(code, true)
+}
+
+fn corrections(code: &str, swaps: bool) -> Vec<String> {
+    if !matches!(validate(code), Err(LuhnError::ChecksumMismatch { .. })) {
+        return Vec::new();
+    }
+    // All digits are ASCII, so each is one byte and can be replaced in place.
+    let positions: Vec<usize> = code
+        .bytes()
+        .enumerate()
+        .filter(|(_, byte)| byte.is_ascii_digit())
+        .map(|(i, _)| i)
+        .collect();
+    let mut found = Vec::new();
+    let mut candidate = code.as_bytes().to_vec();
+    let mut try_candidate = |candidate: &[u8]| {
+        // Not `is_valid_bytes`: the separators may be Unicode whitespace.
+        let candidate = std::str::from_utf8(candidate).expect("only ASCII digits changed");
+        if is_valid(candidate) {
+            found.push(candidate.to_string());
+        }
     };
+    for &i in &positions {
+        let original = candidate[i];
+        for dig in (b'0'..=b'9').filter(|&dig| dig != original) {
+            candidate[i] = dig;
+            try_candidate(&candidate);
+        }
+        candidate[i] = original;
+    }
+    if swaps {
+        for pair in positions.windows(2) {
+            let (i, j) = (pair[0], pair[1]);
+            if candidate[i] != candidate[j] {
+                candidate.swap(i, j);
+                try_candidate(&candidate);
+                candidate.swap(i, j);
+            }
+        }
+    }
+    found
+}
+
+/// Which separators to put up with, and how many digits to expect.
+///
+/// The defaults match [`is_valid`]: any whitespace is skipped, nothing else
+/// is, and any number of digits from two up will do.
+///
+/// ```
+/// use luhn::ValidationOptions;
+///
+/// let dashed = ValidationOptions::new().dashes(true).len_range(16, 16);
+/// assert!(dashed.is_valid("4539-3195-0343-6467"));
+/// assert!(!luhn::is_valid("4539-3195-0343-6467"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationOptions {
+    spaces: bool,
+    dashes: bool,
+    dots: bool,
+    min_len: usize,
+    max_len: usize,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValidationOptions {
+    pub const fn new() -> Self {
+        Self {
+            spaces: true,
+            dashes: false,
+            dots: false,
+            min_len: 2,
+            max_len: usize::MAX,
+        }
+    }
+
+    /// Skip whitespace. On by default.
+    pub const fn spaces(self, allow: bool) -> Self {
+        Self {
+            spaces: allow,
+            ..self
+        }
+    }
+
+    /// Skip `-`.
+    pub const fn dashes(self, allow: bool) -> Self {
+        Self {
+            dashes: allow,
+            ..self
+        }
+    }
+
+    /// Skip `.`.
+    pub const fn dots(self, allow: bool) -> Self {
+        Self {
+            dots: allow,
+            ..self
+        }
+    }
+
+    /// How many digits, separators not counted, a code may have. Anything
+    /// under two still fails with [`LuhnError::TooShort`].
+    pub const fn len_range(self, min_len: usize, max_len: usize) -> Self {
+        Self {
+            min_len,
+            max_len,
+            ..self
+        }
+    }
+
+    fn is_separator(&self, ch: char) -> bool {
+        (self.spaces && ch.is_whitespace())
+            || (self.dashes && ch == '-')
+            || (self.dots && ch == '.')
+    }
+
+    pub fn is_valid(&self, code: &str) -> bool {
+        self.validate(code).is_ok()
+    }
+
+    pub fn validate(&self, code: &str) -> Result<(), LuhnError> {
+        let sums = Sums::new(10).extend_with(
+            code.chars(),
+            |ch| self.is_separator(ch),
+            |ch| ch.to_digit(10),
+        )?;
+        let len = sums.count;
+        if len >= 2 && !(self.min_len..=self.max_len).contains(&len) {
+            return Err(LuhnError::LengthOutOfRange {
+                len,
+                min: self.min_len,
+                max: self.max_len,
+            });
+        }
+        sums.check()
+    }
+}
+
+/// A string known to pass the Luhn check. The only ways to get one go
+/// through [`validate`], so with the `serde` feature an invalid number fails
+/// to deserialize instead of reaching the code that uses it.
+///
+/// ```
+/// let code = luhn::ValidatedLuhn::new("4539 3195 0343 6467").unwrap();
+/// assert_eq!(code.as_str(), "4539 3195 0343 6467");
+/// assert!(luhn::ValidatedLuhn::new("4539 3195 0343 6468").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct ValidatedLuhn(String);
+
+impl ValidatedLuhn {
+    pub fn new(code: impl Into<String>) -> Result<Self, LuhnError> {
+        let code = code.into();
+        validate(&code)?;
+        Ok(Self(code))
+    }
+
+    /// The code as given, separators and all.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl TryFrom<String> for ValidatedLuhn {
+    type Error = LuhnError;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        Self::new(code)
+    }
+}
+
+impl TryFrom<&str> for ValidatedLuhn {
+    type Error = LuhnError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        Self::new(code)
+    }
+}
+
+impl From<ValidatedLuhn> for String {
+    fn from(code: ValidatedLuhn) -> Self {
+        code.0
+    }
+}
+
+impl AsRef<str> for ValidatedLuhn {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ValidatedLuhn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Checkers for identifiers that carry a Luhn check digit along with rules
+/// of their own.
+pub mod validators {
+    use super::{LuhnError, Sums};
+    use std::fmt;
 
-    digits.len() > 1
-        && digits
+    /// Why a string isn't an IMEI.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum ImeiError {
+        /// An IMEI has 15 digits; `len` is how many there were.
+        WrongLength {
+            len: usize,
+        },
+        Luhn(LuhnError),
+    }
+
+    impl fmt::Display for ImeiError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                ImeiError::WrongLength { len } => write!(f, "IMEI needs 15 digits, got {len}"),
+                ImeiError::Luhn(err) => err.fmt(f),
+            }
+        }
+    }
+
+    impl std::error::Error for ImeiError {}
+
+    impl From<LuhnError> for ImeiError {
+        fn from(err: LuhnError) -> Self {
+            ImeiError::Luhn(err)
+        }
+    }
+
+    /// A mobile equipment identity: 14 digits and a Luhn check digit.
+    /// Whitespace is skipped.
+    pub fn imei(code: &str) -> Result<(), ImeiError> {
+        let sums = Sums::decimal(code.chars())?;
+        if sums.count != 15 {
+            return Err(ImeiError::WrongLength { len: sums.count });
+        }
+        Ok(sums.check()?)
+    }
+
+    /// Why a string isn't a National Provider Identifier.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum NpiError {
+        /// An NPI has 10 digits; `len` is how many there were.
+        WrongLength {
+            len: usize,
+        },
+        Luhn(LuhnError),
+    }
+
+    impl fmt::Display for NpiError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                NpiError::WrongLength { len } => write!(f, "NPI needs 10 digits, got {len}"),
+                NpiError::Luhn(err) => err.fmt(f),
+            }
+        }
+    }
+
+    impl std::error::Error for NpiError {}
+
+    impl From<LuhnError> for NpiError {
+        fn from(err: LuhnError) -> Self {
+            NpiError::Luhn(err)
+        }
+    }
+
+    /// The issuer prefix that turns an NPI into an ISO 7812 card number.
+    const NPI_PREFIX: &str = "80840";
+
+    /// A US National Provider Identifier: 10 digits, checked as if prefixed
+    /// with `80840`. Whitespace is skipped.
+    pub fn npi(code: &str) -> Result<(), NpiError> {
+        let prefix = Sums::decimal(NPI_PREFIX.chars())?;
+        let sums = prefix.extend(code.chars(), |c| c.to_digit(10))?;
+        let len = sums.count - prefix.count;
+        if len != 10 {
+            return Err(NpiError::WrongLength { len });
+        }
+        Ok(sums.check()?)
+    }
+
+    /// Why a string isn't an ISIN.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum IsinError {
+        /// An ISIN has 12 characters; `len` is how many there were.
+        WrongLength {
+            len: usize,
+        },
+        /// The first two characters have to be letters.
+        InvalidCountryCode,
+        Luhn(LuhnError),
+    }
+
+    impl fmt::Display for IsinError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                IsinError::WrongLength { len } => {
+                    write!(f, "ISIN needs 12 characters, got {len}")
+                }
+                IsinError::InvalidCountryCode => f.write_str("ISIN must start with two letters"),
+                IsinError::Luhn(err) => err.fmt(f),
+            }
+        }
+    }
+
+    impl std::error::Error for IsinError {}
+
+    impl From<LuhnError> for IsinError {
+        fn from(err: LuhnError) -> Self {
+            IsinError::Luhn(err)
+        }
+    }
+
+    /// An International Securities Identification Number: a two-letter
+    /// country code, nine letters or digits, and a check digit. Letters
+    /// count as two digits, A = 10 up to Z = 35, before the Luhn check.
+    /// No whitespace or lowercase allowed.
+    pub fn isin(code: &str) -> Result<(), IsinError> {
+        let len = code.chars().count();
+        if len != 12 {
+            return Err(IsinError::WrongLength { len });
+        }
+        let mut sums = Sums::new(10);
+        for (index, ch) in code.char_indices() {
+            let value = match ch {
+                '0'..='9' => ch as u32 - '0' as u32,
+                // The check digit is always a digit.
+                'A'..='Z' if index < 11 => ch as u32 - 'A' as u32 + 10,
+                _ => return Err(LuhnError::InvalidCharacter { ch, index }.into()),
+            };
+            if index < 2 && value < 10 {
+                return Err(IsinError::InvalidCountryCode);
+            }
+            if value >= 10 {
+                sums = sums.push(value / 10);
+            }
+            sums = sums.push(value % 10);
+        }
+        Ok(sums.check()?)
+    }
+}
+
+/// Luhn mod N: the same algorithm over any alphabet, where each character
+/// stands for its position. Good for alphanumeric serials.
+///
+/// ```
+/// let base36 = luhn::LuhnModN::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap();
+/// let check = base36.check_character("A1B2C3").unwrap();
+/// assert!(base36.is_valid(&format!("A1B2C3{check}")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LuhnModN {
+    alphabet: Vec<char>,
+}
+
+impl LuhnModN {
+    /// `None` unless `alphabet` has at least two characters, all distinct,
+    /// none of them whitespace.
+    pub fn new(alphabet: &str) -> Option<Self> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let distinct = alphabet
             .iter()
-            .rev()
             .enumerate()
-            .map(|(i, &dig)| {
-                if i % 2 == 1 { DOUBLED[dig as usize] } else { dig }
+            .all(|(i, c)| !c.is_whitespace() && !alphabet[..i].contains(c));
+        (alphabet.len() >= 2 && distinct).then_some(Self { alphabet })
+    }
+
+    fn sums(&self, code: &str) -> Result<Sums, LuhnError> {
+        let base = self.alphabet.len() as u32;
+        Sums::of(code.chars(), base, |c| {
+            self.alphabet.iter().position(|&a| a == c).map(|i| i as u32)
+        })
+    }
+
+    pub fn is_valid(&self, code: &str) -> bool {
+        self.validate(code).is_ok()
+    }
+
+    pub fn validate(&self, code: &str) -> Result<(), LuhnError> {
+        self.sums(code)?.check()
+    }
+
+    /// The character that, appended to `partial`, makes it valid.
+    pub fn check_character(&self, partial: &str) -> Option<char> {
+        let sums = self.sums(partial).ok().filter(|sums| sums.count > 0)?;
+        Some(self.alphabet[sums.check_digit() as usize])
+    }
+}
+
+/// The check-digit schemes this crate knows. All of them work on decimal
+/// digits, skip whitespace, and want at least two digits before calling a
+/// code valid.
+///
+/// Luhn is the one on credit cards. Damm and Verhoeff cost a table lookup
+/// per digit but catch every single-digit error and every swap of adjacent
+/// digits, which Luhn doesn't (it can't tell `09` from `90`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    #[default]
+    Luhn,
+    Damm,
+    Verhoeff,
+}
+
+impl Algorithm {
+    /// Check `code`, whose last digit is the check digit.
+    pub fn is_valid(self, code: &str) -> bool {
+        let Some(digits) = decimal_digits(code) else {
+            return false;
+        };
+        match self {
+            Algorithm::Luhn => is_valid(code),
+            _ if digits.clone().count() < 2 => false,
+            Algorithm::Damm => damm(digits) == 0,
+            Algorithm::Verhoeff => verhoeff(digits.rev(), 0) == 0,
+        }
+    }
+
+    /// The digit that, appended to `partial`, makes it valid. `None` if
+    /// `partial` has no digits or anything besides digits and whitespace.
+    pub fn generate(self, partial: &str) -> Option<u32> {
+        let digits = decimal_digits(partial).filter(|digits| digits.clone().next().is_some())?;
+        let check = match self {
+            Algorithm::Luhn => return checksum_digit(partial),
+            // Every row of the table has its zero on the diagonal, so the
+            // running value is itself the digit that brings it back to zero.
+            Algorithm::Damm => damm(digits),
+            Algorithm::Verhoeff => VERHOEFF_INV[verhoeff(digits.rev(), 1)],
+        };
+        Some(check as u32)
+    }
+}
+
+/// The digits of `code`, or `None` if it has anything but digits and
+/// whitespace.
+fn decimal_digits(code: &str) -> Option<impl DoubleEndedIterator<Item = usize> + Clone + '_> {
+    code.chars()
+        .all(|c| c.is_ascii_digit() || c.is_whitespace())
+        .then(|| {
+            code.chars()
+                .filter_map(|c| c.to_digit(10))
+                .map(|d| d as usize)
+        })
+}
+
+/// Damm's weakly totally anti-symmetric quasigroup of order 10.
+static DAMM: [[usize; 10]; 10] = [
+    [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+    [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+    [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+    [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+    [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+    [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+    [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+    [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+    [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+    [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+];
+
+fn damm(digits: impl Iterator<Item = usize>) -> usize {
+    digits.fold(0, |interim, dig| DAMM[interim][dig])
+}
+
+/// Multiplication in the dihedral group D5.
+static VERHOEFF_D: [[usize; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+/// The permutation applied to a digit `i` places from the right, by `i % 8`.
+static VERHOEFF_P: [[usize; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 8, 7, 6, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+];
+
+static VERHOEFF_INV: [usize; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+/// Fold digits given right to left; `offset` is 1 when the check digit
+/// isn't there yet, so everything sits one place further left.
+fn verhoeff(rev_digits: impl Iterator<Item = usize>, offset: usize) -> usize {
+    rev_digits.enumerate().fold(0, |c, (i, dig)| {
+        VERHOEFF_D[c][VERHOEFF_P[(i + offset) % 8][dig]]
+    })
+}
+
+/// Payment card networks, told apart by the issuer prefix (IIN) and length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CardBrand {
+    Visa,
+    Mastercard,
+    AmericanExpress,
+    Discover,
+    DinersClub,
+    Jcb,
+}
+
+impl CardBrand {
+    const ALL: [CardBrand; 6] = [
+        CardBrand::Visa,
+        CardBrand::Mastercard,
+        CardBrand::AmericanExpress,
+        CardBrand::Discover,
+        CardBrand::DinersClub,
+        CardBrand::Jcb,
+    ];
+
+    /// Inclusive IIN ranges. Both ends have the same number of digits, and
+    /// that's how many leading digits of the card get compared.
+    fn prefixes(self) -> &'static [(u32, u32)] {
+        match self {
+            CardBrand::Visa => &[(4, 4)],
+            CardBrand::Mastercard => &[(51, 55), (2221, 2720)],
+            CardBrand::AmericanExpress => &[(34, 34), (37, 37)],
+            CardBrand::Discover => &[(6011, 6011), (644, 649), (65, 65), (622126, 622925)],
+            CardBrand::DinersClub => &[(300, 305), (3095, 3095), (36, 36), (38, 39)],
+            CardBrand::Jcb => &[(3528, 3589)],
+        }
+    }
+
+    fn lengths(self) -> &'static [usize] {
+        match self {
+            CardBrand::Visa => &[13, 16, 19],
+            CardBrand::Mastercard => &[16],
+            CardBrand::AmericanExpress => &[15],
+            CardBrand::Discover | CardBrand::Jcb => &[16, 17, 18, 19],
+            CardBrand::DinersClub => &[14, 15, 16, 17, 18, 19],
+        }
+    }
+
+    fn matches(self, digits: &str) -> bool {
+        self.lengths().contains(&digits.len())
+            && self.prefixes().iter().any(|&(lo, hi)| {
+                let len = lo.ilog10() as usize + 1;
+                digits[..len]
+                    .parse::<u32>()
+                    .is_ok_and(|prefix| (lo..=hi).contains(&prefix))
             })
-            .sum::<u32>()
-            .is_multiple_of(10)
+    }
+}
+
+impl fmt::Display for CardBrand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            CardBrand::Visa => "Visa",
+            CardBrand::Mastercard => "Mastercard",
+            CardBrand::AmericanExpress => "American Express",
+            CardBrand::Discover => "Discover",
+            CardBrand::DinersClub => "Diners Club",
+            CardBrand::Jcb => "JCB",
+        })
+    }
+}
+
+/// Just the digits of a card number, or `None` if it has anything besides
+/// digits and whitespace.
+fn card_digits(number: &str) -> Option<String> {
+    let digits = decimal_digits(number)?;
+    Some(digits.map(|dig| char::from(b'0' + dig as u8)).collect())
+}
+
+/// Which network issued `number`, going by prefix and length alone; the
+/// check digit isn't looked at. Whitespace is ignored.
+pub fn detect_brand(number: &str) -> Option<CardBrand> {
+    let digits = card_digits(number)?;
+    CardBrand::ALL
+        .into_iter()
+        .find(|brand| brand.matches(&digits))
+}
+
+/// Why a string isn't a card number.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CardError {
+    /// It fails the Luhn check.
+    Luhn(LuhnError),
+    /// It passes, but no known brand has that prefix and length.
+    UnknownBrand,
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardError::Luhn(err) => err.fmt(f),
+            CardError::UnknownBrand => f.write_str("not a recognised card number"),
+        }
+    }
+}
+
+impl std::error::Error for CardError {}
+
+impl From<LuhnError> for CardError {
+    fn from(err: LuhnError) -> Self {
+        CardError::Luhn(err)
+    }
+}
+
+/// A card number that passes the Luhn check and belongs to a known brand.
+///
+/// Neither `Display` nor `Debug` show the whole number, only the last four
+/// digits, so these are safe to log. Ask for [`grouped`](Self::grouped) or
+/// [`digits`](Self::digits) when you really need it.
+///
+/// ```
+/// let card = luhn::CardNumber::parse("4539319503436467").unwrap();
+/// assert_eq!(card.to_string(), "•••• •••• •••• 6467");
+/// assert_eq!(card.grouped(), "4539 3195 0343 6467");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CardNumber {
+    digits: String,
+    brand: CardBrand,
+}
+
+impl CardNumber {
+    /// Parse `number`, ignoring whitespace.
+    pub fn parse(number: &str) -> Result<Self, CardError> {
+        validate(number)?;
+        let brand = detect_brand(number).ok_or(CardError::UnknownBrand)?;
+        let digits = card_digits(number).ok_or(CardError::UnknownBrand)?;
+        Ok(Self { digits, brand })
+    }
+
+    pub fn brand(&self) -> CardBrand {
+        self.brand
+    }
+
+    /// The bare digits, with no spacing.
+    pub fn digits(&self) -> &str {
+        &self.digits
+    }
+
+    /// The full number, spaced the way it's printed on the card.
+    pub fn grouped(&self) -> String {
+        let mut out = String::new();
+        self.write_groups(&mut out, false)
+            .expect("writing to a String");
+        out
+    }
+
+    /// Spaced like [`grouped`](Self::grouped), but with all except the last
+    /// four digits blotted out.
+    pub fn masked(&self) -> String {
+        self.to_string()
+    }
+
+    /// How many digits go in each printed group: Amex and 14-digit Diners
+    /// cards use 4-6-rest, everything else fours.
+    fn group_lengths(&self) -> impl Iterator<Item = usize> + '_ {
+        let len = self.digits.len();
+        let four_six = self.brand == CardBrand::AmericanExpress
+            || (self.brand == CardBrand::DinersClub && len == 14);
+        let sizes: &[usize] = if four_six { &[4, 6, usize::MAX] } else { &[] };
+        sizes.iter().copied().chain(std::iter::repeat(4))
+    }
+
+    fn write_groups(&self, out: &mut impl fmt::Write, mask: bool) -> fmt::Result {
+        let shown_from = self.digits.len().saturating_sub(4);
+        let mut rest = &self.digits[..];
+        let mut at = 0;
+        for size in self.group_lengths() {
+            if rest.is_empty() {
+                break;
+            }
+            if at > 0 {
+                out.write_char(' ')?;
+            }
+            let (group, tail) = rest.split_at(size.min(rest.len()));
+            for (i, ch) in group.chars().enumerate() {
+                out.write_char(if mask && at + i < shown_from {
+                    '•'
+                } else {
+                    ch
+                })?;
+            }
+            at += group.len();
+            rest = tail;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CardNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_groups(f, true)
+    }
+}
+
+impl fmt::Debug for CardNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CardNumber")
+            .field("digits", &format_args!("{self}"))
+            .field("brand", &self.brand)
+            .finish()
+    }
 }
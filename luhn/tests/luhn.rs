@@ -109,3 +109,533 @@ fn using_ascii_value_for_doubled_non_digit_isn_t_allowed() {
 fn non_numeric_non_space_char_in_the_middle_with_a_sum_that_s_divisible_by_10_isn_t_allowed() {
     assert!(!is_valid("59%59"));
 }
+
+#[test]
+fn checksum_digit_of_a_valid_card() {
+    assert_eq!(checksum_digit("4539 3195 0343 646"), Some(7));
+}
+
+#[test]
+fn checksum_digit_can_be_zero() {
+    assert_eq!(checksum_digit("05"), Some(9));
+    assert_eq!(checksum_digit("1"), Some(8));
+    assert_eq!(checksum_digit("0"), Some(0));
+}
+
+#[test]
+fn checksum_digit_rejects_bad_input() {
+    assert_eq!(checksum_digit(""), None);
+    assert_eq!(checksum_digit("   "), None);
+    assert_eq!(checksum_digit("12a4"), None);
+}
+
+#[test]
+fn appended_check_digit_makes_a_valid_number() {
+    for partial in ["7992739871", "055 444 28", "1", "8273 1232 7352 056"] {
+        let code = append_check_digit(partial).unwrap();
+        assert!(is_valid(&code), "{code} should be valid");
+        assert_eq!(&code[..partial.len()], partial);
+    }
+}
+
+#[test]
+fn append_check_digit_rejects_bad_input() {
+    assert_eq!(append_check_digit("12-34"), None);
+}
+
+#[test]
+fn is_valid_chars_agrees_with_is_valid() {
+    for code in [
+        "059",
+        "59",
+        "055 444 285",
+        "055 444 286",
+        "0",
+        " 0",
+        "12a4",
+        "",
+    ] {
+        assert_eq!(is_valid_chars(code.chars()), is_valid(code), "{code:?}");
+    }
+}
+
+#[test]
+fn is_valid_chars_handles_huge_inputs() {
+    // 0 then a million 9-1 pairs: every "91" pair sums to 10 on its own.
+    let digits = std::iter::once('0').chain(std::iter::repeat_n(['9', '1'], 1_000_000).flatten());
+    assert!(is_valid_chars(digits));
+}
+
+#[test]
+fn validate_accepts_a_valid_number() {
+    assert_eq!(validate("4539 3195 0343 6467"), Ok(()));
+}
+
+#[test]
+fn validate_reports_the_invalid_character() {
+    assert_eq!(
+        validate("055-444-285"),
+        Err(LuhnError::InvalidCharacter { ch: '-', index: 3 })
+    );
+    assert_eq!(
+        validate("é1"),
+        Err(LuhnError::InvalidCharacter { ch: 'é', index: 0 })
+    );
+}
+
+#[test]
+fn validate_reports_too_short() {
+    assert_eq!(validate(" 0"), Err(LuhnError::TooShort { len: 1 }));
+    assert_eq!(validate(""), Err(LuhnError::TooShort { len: 0 }));
+}
+
+#[test]
+fn validate_reports_the_checksum() {
+    assert_eq!(
+        validate("8273 1232 7352 0569"),
+        Err(LuhnError::ChecksumMismatch { sum: 57 })
+    );
+}
+
+#[test]
+fn luhn_errors_explain_themselves() {
+    let err = validate("12a4").unwrap_err();
+    assert_eq!(err.to_string(), "unexpected character 'a' at position 2");
+}
+
+#[test]
+fn mod_n_with_decimal_alphabet_matches_luhn() {
+    let decimal = LuhnModN::new("0123456789").unwrap();
+    for code in [
+        "059",
+        "59",
+        "055 444 285",
+        "055 444 286",
+        "8273 1232 7352 0569",
+    ] {
+        assert_eq!(decimal.is_valid(code), is_valid(code), "{code}");
+    }
+    assert_eq!(decimal.check_character("4539 3195 0343 646"), Some('7'));
+}
+
+#[test]
+fn mod_n_check_character() {
+    // The worked example for Luhn mod N.
+    let hex_ish = LuhnModN::new("abcdef").unwrap();
+    assert_eq!(hex_ish.check_character("abcdef"), Some('e'));
+    assert!(hex_ish.is_valid("abcdefe"));
+    assert!(!hex_ish.is_valid("abcdefa"));
+}
+
+#[test]
+fn mod_n_base_36_serials() {
+    let base36 = LuhnModN::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap();
+    for partial in ["A1B2C3", "ZZZZ", "0", "SERIAL 42"] {
+        let check = base36.check_character(partial).unwrap();
+        assert!(
+            base36.is_valid(&format!("{partial}{check}")),
+            "{partial}{check}"
+        );
+    }
+    assert_eq!(
+        base36.validate("AB-C"),
+        Err(LuhnError::InvalidCharacter { ch: '-', index: 2 })
+    );
+}
+
+#[test]
+fn mod_n_rejects_bad_alphabets() {
+    assert_eq!(LuhnModN::new("a"), None);
+    assert_eq!(LuhnModN::new("abca"), None);
+    assert_eq!(LuhnModN::new("ab c"), None);
+}
+
+#[test]
+fn algorithm_luhn_matches_free_functions() {
+    for code in [
+        "059",
+        "059a",
+        "0",
+        "4539 3195 0343 6467",
+        "8273 1232 7352 0569",
+    ] {
+        assert_eq!(Algorithm::Luhn.is_valid(code), is_valid(code), "{code}");
+    }
+    assert_eq!(Algorithm::Luhn.generate("4539 3195 0343 646"), Some(7));
+}
+
+#[test]
+fn damm_check_digit() {
+    assert_eq!(Algorithm::Damm.generate("572"), Some(4));
+    assert!(Algorithm::Damm.is_valid("5724"));
+    assert!(Algorithm::Damm.is_valid("57 24"));
+    assert!(!Algorithm::Damm.is_valid("5723"));
+    assert!(!Algorithm::Damm.is_valid("0"));
+}
+
+#[test]
+fn verhoeff_check_digit() {
+    assert_eq!(Algorithm::Verhoeff.generate("236"), Some(3));
+    assert!(Algorithm::Verhoeff.is_valid("2363"));
+    assert!(!Algorithm::Verhoeff.is_valid("2364"));
+    assert_eq!(Algorithm::Verhoeff.generate("12345"), Some(1));
+    assert!(Algorithm::Verhoeff.is_valid("123451"));
+}
+
+#[test]
+fn damm_and_verhoeff_catch_what_luhn_misses() {
+    // Swapping 0 and 9 leaves the Luhn sum unchanged.
+    assert_eq!(
+        Algorithm::Luhn.generate("1090"),
+        Algorithm::Luhn.generate("1900")
+    );
+    for algorithm in [Algorithm::Damm, Algorithm::Verhoeff] {
+        let check = algorithm.generate("1090").unwrap();
+        assert!(algorithm.is_valid(&format!("1090{check}")));
+        assert!(
+            !algorithm.is_valid(&format!("1900{check}")),
+            "{algorithm:?}"
+        );
+    }
+}
+
+#[test]
+fn algorithms_reject_non_digits() {
+    for algorithm in [Algorithm::Luhn, Algorithm::Damm, Algorithm::Verhoeff] {
+        assert!(!algorithm.is_valid("57-24"));
+        assert_eq!(algorithm.generate("57-2"), None);
+        assert_eq!(algorithm.generate(" "), None);
+    }
+}
+
+#[test]
+fn detects_card_brands() {
+    assert_eq!(detect_brand("4539 3195 0343 6467"), Some(CardBrand::Visa));
+    assert_eq!(
+        detect_brand("5555555555554444"),
+        Some(CardBrand::Mastercard)
+    );
+    assert_eq!(
+        detect_brand("2223003122003222"),
+        Some(CardBrand::Mastercard)
+    );
+    assert_eq!(
+        detect_brand("3782 822463 10005"),
+        Some(CardBrand::AmericanExpress)
+    );
+    assert_eq!(detect_brand("6011111111111117"), Some(CardBrand::Discover));
+    assert_eq!(detect_brand("30569309025904"), Some(CardBrand::DinersClub));
+    assert_eq!(detect_brand("3530111333300000"), Some(CardBrand::Jcb));
+}
+
+#[test]
+fn brand_needs_prefix_and_length() {
+    // Amex prefix, Visa length.
+    assert_eq!(detect_brand("3782822463100050"), None);
+    assert_eq!(detect_brand("4539"), None);
+    assert_eq!(detect_brand("9999999999999995"), None);
+    assert_eq!(detect_brand("4539-3195-0343-6467"), None);
+}
+
+#[test]
+fn parse_card_number() {
+    let card = CardNumber::parse("4539 3195 0343 6467").unwrap();
+    assert_eq!(card.brand(), CardBrand::Visa);
+    assert_eq!(card.digits(), "4539319503436467");
+}
+
+#[test]
+fn parse_card_number_errors() {
+    assert_eq!(
+        CardNumber::parse("4539 3195 0343 6468"),
+        Err(CardError::Luhn(LuhnError::ChecksumMismatch { sum: 81 }))
+    );
+    // Valid Luhn, but no such brand.
+    assert_eq!(CardNumber::parse("059"), Err(CardError::UnknownBrand));
+}
+
+#[test]
+fn card_number_masked_and_grouped() {
+    let card = CardNumber::parse("4539 3195 0343 6467").unwrap();
+    assert_eq!(card.masked(), "•••• •••• •••• 6467");
+    assert_eq!(card.grouped(), "4539 3195 0343 6467");
+}
+
+#[test]
+fn amex_groups_four_six_five() {
+    let card = CardNumber::parse("378282246310005").unwrap();
+    assert_eq!(card.grouped(), "3782 822463 10005");
+    assert_eq!(card.masked(), "•••• •••••• •0005");
+}
+
+#[test]
+fn card_number_never_formats_full_number() {
+    let card = CardNumber::parse("4539319503436467").unwrap();
+    assert_eq!(card.to_string(), "•••• •••• •••• 6467");
+    let debug = format!("{card:?}");
+    assert!(!debug.contains("4539"), "{debug}");
+    assert!(debug.contains("6467"), "{debug}");
+}
+
+const VISA_IS_VALID: bool = is_valid_bytes(b"4539 3195 0343 6467");
+const _: () = assert!(VISA_IS_VALID);
+
+#[test]
+fn valid_bytes_matches_is_valid_on_ascii() {
+    for code in [
+        "059",
+        "59",
+        "0",
+        " 0",
+        "055 444 285",
+        "055-444-285",
+        "095 245 88",
+        "091",
+    ] {
+        assert_eq!(is_valid_bytes(code.as_bytes()), is_valid(code), "{code}");
+    }
+}
+
+#[test]
+fn is_valid_agrees_with_validate_on_ascii_whitespace() {
+    for code in ["0\x0B0", "0\t0", "0\x0C0", "0\r\n0", "0\x1F0"] {
+        assert_eq!(is_valid(code), validate(code).is_ok(), "{code:?}");
+        assert_eq!(is_valid(code), is_valid_chars(code.chars()), "{code:?}");
+    }
+    assert!(is_valid("0\x0B0"));
+}
+
+#[test]
+fn valid_bytes_rejects_non_ascii() {
+    // A non-breaking space is whitespace to `is_valid`, but not here.
+    assert!(!is_valid_bytes("0\u{a0}59".as_bytes()));
+    assert!(!is_valid_bytes(b"05\xff9"));
+}
+
+#[test]
+fn non_ascii_whitespace_is_still_skipped() {
+    assert!(is_valid("0\u{a0}59"));
+    assert!(is_valid("4539\u{2003}3195\u{2003}0343\u{2003}6467"));
+    assert!(!is_valid("0٥9"));
+}
+
+#[test]
+fn validate_batch_keeps_order() {
+    let codes = [
+        "059",
+        "59",
+        "4539 3195 0343 6467",
+        "055-444-285",
+        "8273 1232 7352 0569",
+    ];
+    assert_eq!(validate_batch(codes), codes.map(is_valid));
+}
+
+#[test]
+fn validate_batch_many() {
+    let codes: Vec<String> = (0..10_000).map(|n| format!("{n:06}")).collect();
+    let valid = validate_batch(codes.iter().map(String::as_str));
+    assert_eq!(valid.len(), codes.len());
+    assert_eq!(valid.iter().filter(|&&v| v).count(), 1000);
+}
+
+#[test]
+fn imei() {
+    use luhn::validators::{ImeiError, imei};
+
+    assert_eq!(imei("490154203237518"), Ok(()));
+    assert_eq!(imei("49 015420 323751 8"), Ok(()));
+    assert_eq!(
+        imei("490154203237519"),
+        Err(ImeiError::Luhn(LuhnError::ChecksumMismatch { sum: 61 }))
+    );
+    // Luhn-valid, but too short.
+    assert_eq!(imei("059"), Err(ImeiError::WrongLength { len: 3 }));
+}
+
+#[test]
+fn npi() {
+    use luhn::validators::{NpiError, npi};
+
+    assert_eq!(npi("1234567893"), Ok(()));
+    assert!(matches!(npi("1234567890"), Err(NpiError::Luhn(_))));
+    assert_eq!(npi("059"), Err(NpiError::WrongLength { len: 3 }));
+    assert_eq!(
+        npi("12345-67893"),
+        Err(NpiError::Luhn(LuhnError::InvalidCharacter {
+            ch: '-',
+            index: 5
+        }))
+    );
+}
+
+#[test]
+fn isin() {
+    use luhn::validators::{IsinError, isin};
+
+    assert_eq!(isin("US0378331005"), Ok(()));
+    assert_eq!(isin("AU0000XVGZA3"), Ok(()));
+    assert_eq!(isin("GB0002634946"), Ok(()));
+    assert!(matches!(isin("US0378331004"), Err(IsinError::Luhn(_))));
+    assert_eq!(isin("US037833100"), Err(IsinError::WrongLength { len: 11 }));
+    assert_eq!(isin("120378331005"), Err(IsinError::InvalidCountryCode));
+    assert_eq!(
+        isin("us0378331005"),
+        Err(IsinError::Luhn(LuhnError::InvalidCharacter {
+            ch: 'u',
+            index: 0
+        }))
+    );
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn generated_numbers_are_valid() {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(1092);
+    for _ in 0..100 {
+        let code = generate("4539", 16, &mut rng);
+        assert_eq!(code.len(), 16);
+        assert!(code.starts_with("4539"), "{code}");
+        assert!(is_valid(&code), "{code}");
+        assert_eq!(detect_brand(&code), Some(CardBrand::Visa));
+    }
+    assert!(is_valid(&generate("", 2, &mut rng)));
+    assert_eq!(generate("05", 3, &mut rng), "059");
+}
+
+#[cfg(feature = "rand")]
+#[test]
+#[should_panic]
+fn generate_needs_room_for_the_check_digit() {
+    let mut rng = rand::rng();
+    generate("4539", 4, &mut rng);
+}
+
+#[test]
+fn default_options_match_is_valid() {
+    let options = ValidationOptions::default();
+    for code in [
+        "059",
+        "0",
+        " 0",
+        "055 444 285",
+        "055-444-285",
+        "055.444.285",
+        "8273 1232 7352 0569",
+    ] {
+        assert_eq!(options.is_valid(code), is_valid(code), "{code}");
+    }
+}
+
+#[test]
+fn options_allow_dashes_and_dots() {
+    let dashed = ValidationOptions::new().dashes(true);
+    assert!(dashed.is_valid("4539-3195-0343-6467"));
+    assert!(!dashed.is_valid("4539.3195.0343.6467"));
+
+    let dotted = ValidationOptions::new().dots(true);
+    assert!(dotted.is_valid("055.444.285"));
+    assert_eq!(
+        dotted.validate("055-444-285"),
+        Err(LuhnError::InvalidCharacter { ch: '-', index: 3 })
+    );
+}
+
+#[test]
+fn options_can_refuse_spaces() {
+    let compact = ValidationOptions::new().spaces(false);
+    assert!(compact.is_valid("4539319503436467"));
+    assert_eq!(
+        compact.validate("4539 3195 0343 6467"),
+        Err(LuhnError::InvalidCharacter { ch: ' ', index: 4 })
+    );
+}
+
+#[test]
+fn options_length_range() {
+    let cards = ValidationOptions::new().dashes(true).len_range(13, 19);
+    assert!(cards.is_valid("4539-3195-0343-6467"));
+    assert_eq!(
+        cards.validate("055 444 285"),
+        Err(LuhnError::LengthOutOfRange {
+            len: 9,
+            min: 13,
+            max: 19
+        })
+    );
+    assert_eq!(cards.validate("0"), Err(LuhnError::TooShort { len: 1 }));
+}
+
+#[test]
+fn validated_luhn_only_holds_valid_codes() {
+    let code = ValidatedLuhn::try_from("055 444 285").unwrap();
+    assert_eq!(code.to_string(), "055 444 285");
+    assert_eq!(String::from(code), "055 444 285");
+    assert!(matches!(
+        ValidatedLuhn::try_from(String::from("055 444 286")),
+        Err(LuhnError::ChecksumMismatch { .. })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn validated_luhn_deserializes_only_valid_codes() {
+    #[derive(serde::Deserialize)]
+    struct Payment {
+        card: ValidatedLuhn,
+    }
+
+    let payment: Payment = serde_json::from_str(r#"{"card": "4539 3195 0343 6467"}"#).unwrap();
+    assert_eq!(payment.card.as_str(), "4539 3195 0343 6467");
+
+    let err = serde_json::from_str::<Payment>(r#"{"card": "4539 3195 0343 6468"}"#)
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("checksum"), "{err}");
+    assert!(serde_json::from_str::<Payment>(r#"{"card": 4539}"#).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn validated_luhn_serializes_as_a_string() {
+    let code = ValidatedLuhn::new("059").unwrap();
+    assert_eq!(serde_json::to_string(&code).unwrap(), r#""059""#);
+}
+
+#[test]
+fn suggests_single_digit_fixes() {
+    // Exactly one replacement works at each digit position.
+    assert_eq!(
+        suggest_corrections("059 1"),
+        ["759 1", "009 1", "052 1", "059 6"]
+    );
+}
+
+#[test]
+fn suggests_fixes_around_unicode_whitespace() {
+    let fixes = suggest_corrections("055\u{a0}444 286");
+    assert!(fixes.contains(&"055\u{a0}444 285".to_string()), "{fixes:?}");
+    assert!(fixes.iter().all(|fix| is_valid(fix)));
+}
+
+#[test]
+fn suggests_nothing_for_valid_or_malformed_codes() {
+    assert!(suggest_corrections("055 444 285").is_empty());
+    assert!(suggest_corrections("055-444-286").is_empty());
+    assert!(suggest_corrections("0").is_empty());
+}
+
+#[test]
+fn suggests_swapped_neighbours() {
+    // 4539 3195 0343 6467 with the last two digits swapped.
+    let code = "4539 3195 0343 6476";
+    assert!(!suggest_corrections(code).contains(&"4539 3195 0343 6467".to_string()));
+    let fixes = suggest_corrections_with_swaps(code);
+    assert!(
+        fixes.contains(&"4539 3195 0343 6467".to_string()),
+        "{fixes:?}"
+    );
+    assert!(fixes.iter().all(|fix| is_valid(fix)));
+}
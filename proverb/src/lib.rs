@@ -1,14 +1,392 @@
-pub fn build_proverb(list: &[&str]) -> String { // [R] list (borrowed slice)
-    if list.is_empty() {
-        return String::new();
+use std::fmt::{self, Display, Write};
+use std::io;
+
+/// The usual wording, split at each `{}`.
+const LINE: [&str; 3] = ["For want of a ", " the ", " was lost."];
+const LAST: [&str; 2] = ["And all for the want of a ", "."];
+
+/// The proverb for `items`: a slice, array, `Vec` or any other iterator of
+/// things to display, like `build_proverb(["nail", "shoe"])` or
+/// `build_proverb(names.iter().map(String::as_str))`.
+///
+/// This used to take only `&[&str]`. Being generic means a bare `&[]` no
+/// longer says what it holds, so an empty slice needs its type spelled out:
+///
+/// ```
+/// let none: &[&str] = &[];
+/// assert_eq!(proverb::build_proverb(none), "");
+/// ```
+pub fn build_proverb<I, T>(items: I) -> String // [RWO] items (owned iterator)
+where
+    I: IntoIterator<Item = T>,
+    T: Display,
+{
+    ProverbBuilder::new().build(items)
+}
+
+/// [`build_proverb`], with `qualifier` in front of the first item on the
+/// last line: "And all for the want of a horseshoe nail."
+pub fn build_proverb_with_qualifier<I, T>(items: I, qualifier: &str) -> String // [RWO] items (owned iterator); [R] qualifier
+where
+    I: IntoIterator<Item = T>,
+    T: Display,
+{
+    ProverbBuilder::new().qualifier(qualifier).build(items)
+}
+
+/// Why a template can't be used for a proverb line.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TemplateError {
+    /// Each `{}` is filled with an item; the template needs exactly
+    /// `expected` of them.
+    Placeholders { expected: usize, found: usize },
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::Placeholders { expected, found } => {
+                write!(f, "expected {expected} {{}} placeholders, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// A proverb with its own wording. Each chained line fills its two `{}`
+/// with what was wanted and what was lost; the last line fills its one
+/// `{}` with the first item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProverbBuilder {
+    line: Vec<String>,         // [RWO] the line template, split at each {}
+    last: Vec<String>,         // [RWO] the last line template, split at each {}
+    grammar: Grammar,          // [RWO] grammar (owned)
+    qualifier: Option<String>, // [RWO] qualifier (owned)
+}
+
+impl Default for ProverbBuilder {
+    fn default() -> Self {
+        Self {
+            line: LINE.map(String::from).to_vec(),
+            last: LAST.map(String::from).to_vec(),
+            grammar: Grammar::PLAIN,
+            qualifier: None,
+        }
+    }
+}
+
+impl ProverbBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The wording of each chained line, like
+    /// `"For want of a {} the {} was lost."`.
+    pub fn line(mut self, template: &str) -> Result<Self, TemplateError> { // [R] template (borrowed str)
+        self.line = checked(template, 2)?;
+        Ok(self)
+    }
+
+    /// The wording of the last line, like `"And all for the want of a {}."`.
+    pub fn last_line(mut self, template: &str) -> Result<Self, TemplateError> { // [R] template (borrowed str)
+        self.last = checked(template, 1)?;
+        Ok(self)
+    }
+
+    /// Whether to pick `a` or `an` to suit each item, where the template
+    /// has one of them right in front of a `{}`. It goes by spelling, so
+    /// "an apple" but "a hour". Off by default: the template's article
+    /// stays as written.
+    pub fn auto_articles(mut self, on: bool) -> Self {
+        self.grammar.auto_articles = on;
+        self
+    }
+
+    /// Items that are plural. They get no article at all, and a `was` right
+    /// after one becomes `were`: "For want of nails the shoe was lost.",
+    /// "For want of a shoe the nails were lost."
+    pub fn plural<S: Into<String>>(mut self, items: impl IntoIterator<Item = S>) -> Self { // [RWO] items (owned iterator)
+        self.grammar.plurals.extend(items.into_iter().map(Into::into));
+        self
+    }
+
+    /// A word or two to put in front of the first item when it comes back
+    /// on the last line, like `horseshoe` for a `nail`.
+    pub fn qualifier(mut self, qualifier: impl Into<String>) -> Self { // [RWO] qualifier (owned)
+        self.qualifier = Some(qualifier.into());
+        self
+    }
+
+    pub fn build<I, T>(&self, items: I) -> String // [RWO] items (owned iterator)
+    where
+        I: IntoIterator<Item = T>,
+        T: Display,
+    {
+        let items: Vec<T> = items.into_iter().collect(); // [RWO] items (owned)
+        let mut proverb = String::new();                  // [RWO] proverb (owned)
+        let qualifier = self.qualifier.as_deref(); // [R] qualifier (borrowed str)
+        write_lines(&mut proverb, &self.line, &self.last, &self.grammar, qualifier, &items).expect("a String takes any amount of text");
+        proverb
+    } // drop: items
+}
+
+/// A proverb in the usual wording, written out line by line as it's
+/// formatted rather than built up in a `String` first.
+#[derive(Debug, Clone, Copy)]
+pub struct Proverb<'a, T> {
+    items: &'a [T], // [R] items (borrowed slice)
+}
+
+impl<'a, T: Display> Proverb<'a, T> {
+    pub fn new(items: &'a [T]) -> Self {
+        Self { items }
+    }
+}
+
+impl<T: Display> Display for Proverb<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_lines(f, &LINE, &LAST, &Grammar::PLAIN, None, self.items)
+    }
+}
+
+/// The lines of [`build_proverb`], one at a time and only as they're
+/// asked for, without the newlines.
+pub fn proverb_lines<I, T>(items: I) -> impl Iterator<Item = String> // [RWO] items (owned iterator)
+where
+    I: IntoIterator<Item = T>,
+    T: Display,
+{
+    let mut items = items.into_iter();                          // [RWO] items (owned iterator)
+    let mut want = items.next();                                // [RWO] want (owned T)
+    let mut first = want.as_ref().map(|want| want.to_string()); // [RWO] first (owned String)
+    std::iter::from_fn(move || {
+        let mut line = String::new(); // [RWO] line (owned)
+        match (want.take()?, items.next()) {
+            (wanted, Some(lost)) => { // [RWO] wanted, lost (owned T)
+                fill(&mut line, &LINE, &Grammar::PLAIN, &[&wanted, &lost]).ok()?;
+                want = Some(lost);
+            } // moves: lost into want; drop: wanted
+            (_, None) => fill(&mut line, &LAST, &Grammar::PLAIN, &[&first.take()?]).ok()?,
+        }
+        Some(line)
+    })
+}
+
+/// One line for each `(want, lost)` pair, in order, then the usual last
+/// line for the first pair's `want`. The pairs don't have to chain.
+pub fn build_proverb_from_pairs<I, T>(pairs: I) -> String // [RWO] pairs (owned iterator)
+where
+    I: IntoIterator<Item = (T, T)>,
+    T: Display,
+{
+    let mut proverb = String::new(); // [RWO] proverb (owned)
+    let mut first = None;            // [RWO] first (owned String, once there's a pair)
+    for (want, lost) in pairs { // [RWO] want, lost (owned T)
+        fill(&mut proverb, &LINE, &Grammar::PLAIN, &[&want, &lost]).expect("a String takes any amount of text");
+        proverb.push('\n');
+        first.get_or_insert_with(|| want.to_string());
+    } // drop: want, lost
+    if let Some(first) = first { // [RWO] first (owned String)
+        fill(&mut proverb, &LAST, &Grammar::PLAIN, &[&first]).expect("a String takes any amount of text");
+    }
+    proverb
+}
+
+/// Writes the proverb straight into `out`, one line at a time, with the
+/// same text as [`build_proverb`]: no newline after the last line.
+pub fn write_proverb<I, T, W>(items: I, out: &mut W) -> fmt::Result // [RWO] items (owned iterator); [RW] out
+where
+    I: IntoIterator<Item = T>,
+    T: Display,
+    W: Write,
+{
+    let mut items = items.into_iter(); // [RWO] items (owned iterator)
+    let Some(mut want) = items.next() else { // [RWO] want (owned T)
+        return Ok(());
+    };
+    let mut first = String::new(); // [RWO] first (owned): want moves on down the chain
+    write!(first, "{want}")?; // not to_string, which panics if Display fails
+    for lost in items { // [RWO] lost (owned T)
+        fill(out, &LINE, &Grammar::PLAIN, &[&want, &lost])?;
+        out.write_char('\n')?;
+        want = lost;
+    } // moves: lost into want
+    fill(out, &LAST, &Grammar::PLAIN, &[&first])
+} // drop: want, first
+
+/// [`write_proverb`] for byte streams like files, sockets and stdout.
+pub fn write_proverb_io<I, T, W>(items: I, out: &mut W) -> io::Result<()> // [RWO] items (owned iterator); [RW] out
+where
+    I: IntoIterator<Item = T>,
+    T: Display,
+    W: io::Write,
+{
+    let mut adapter = IoAdapter { out, error: Ok(()) }; // [RWO] adapter (owned, borrows out)
+    match write_proverb(items, &mut adapter) {
+        Ok(()) => Ok(()),
+        // No I/O error stashed means an item's Display gave up.
+        Err(fmt::Error) => adapter.error.and(Err(io::Error::other("formatter error"))),
     }
+} // borrow ends: out; drop: adapter
 
-    let mut proverb = Vec::<String>::new(); // [RWO] proverb (owned)
-    for pair in list.windows(2) {           // [R] pair (borrowed slice)
-        let [want, lost] = pair else { unreachable!() }; // [R] want, lost (borrowed &str)
-        proverb.push(format!("For want of a {want} the {lost} was lost."))
+/// Lets `fmt::Write` code write to an `io::Write`, keeping hold of the
+/// I/O error that `fmt::Error` has no room for.
+struct IoAdapter<'a, W> {
+    out: &'a mut W,          // [RW] out (mutably borrowed)
+    error: io::Result<()>,   // [RWO] error (owned)
+}
+
+impl<W: io::Write> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Err(error);
+            fmt::Error
+        })
+    }
+}
+
+/// The proverb for `items`, each line filled in from `line` and the last
+/// one from `last`, with a newline between lines.
+fn write_lines<S, T>(
+    out: &mut impl Write,       // [RW] out (mutably borrowed)
+    line: &[S],                 // [R] line (borrowed slice)
+    last: &[S],                 // [R] last (borrowed slice)
+    grammar: &Grammar,          // [R] grammar (borrowed)
+    qualifier: Option<&str>,    // [R] qualifier (borrowed str)
+    items: &[T],                // [R] items (borrowed slice)
+) -> fmt::Result
+where
+    S: AsRef<str>,
+    T: Display,
+{
+    let Some(first) = items.first() else { // [R] first (borrowed T)
+        return Ok(());
+    };
+    for pair in items.windows(2) { // [R] pair (borrowed slice)
+        let [want, lost] = pair else { unreachable!() }; // [R] want, lost (borrowed T)
+        fill(out, line, grammar, &[want, lost])?;
+        out.write_char('\n')?;
     } // borrows end: pair, want, lost
+    match qualifier {
+        Some(qualifier) => fill(out, last, grammar, &[&format_args!("{qualifier} {first}")]),
+        None => fill(out, last, grammar, &[first]),
+    }
+} // borrow ends: first
+
+fn split(template: &str) -> Vec<String> { // [R] template (borrowed str)
+    template.split("{}").map(String::from).collect()
+}
+
+fn checked(template: &str, expected: usize) -> Result<Vec<String>, TemplateError> { // [R] template (borrowed str)
+    let parts = split(template); // [RWO] parts (owned)
+    let found = parts.len() - 1;
+    if found == expected {
+        Ok(parts)
+    } else {
+        Err(TemplateError::Placeholders { expected, found })
+    }
+}
+
+/// `parts` with each of `items` between them in turn, fixing up the
+/// articles and verbs as `grammar` says.
+fn fill<S: AsRef<str>>(out: &mut impl Write, parts: &[S], grammar: &Grammar, items: &[&dyn Display]) -> fmt::Result { // [RW] out; [R] parts, grammar, items
+    let mut after_plural = false;
+    for (part, item) in parts.iter().zip(items) { // [R] part, item (borrowed)
+        if grammar.is_plain() {
+            write!(out, "{}{item}", part.as_ref())?;
+            continue;
+        }
+        let item = item.to_string(); // [RWO] item (owned)
+        let part = agree(out, part.as_ref(), after_plural)?; // [R] part (reborrowed past the verb)
+        match article_at_end(part) {
+            Some((before, article)) => {
+                out.write_str(before)?;
+                out.write_str(grammar.article(article, &item))?;
+            }
+            None => out.write_str(part)?,
+        }
+        out.write_str(&item)?;
+        after_plural = grammar.is_plural(&item);
+    } // borrows end: part; drop: item
+    let last = agree(out, parts[parts.len() - 1].as_ref(), after_plural)?; // [R] last (borrowed str)
+    out.write_str(last)
+}
+
+/// Writes `were` for a ` was` leading `part` when it follows a plural item,
+/// and hands back what's left of `part` to write.
+fn agree<'a>(out: &mut impl Write, part: &'a str, after_plural: bool) -> Result<&'a str, fmt::Error> { // [RW] out; [R] part (borrowed str)
+    let Some(rest) = part.strip_prefix(" was").filter(|_| after_plural) else {
+        return Ok(part);
+    };
+    if rest.starts_with(|c: char| c.is_alphanumeric()) {
+        return Ok(part);
+    }
+    out.write_str(" were")?;
+    Ok(rest)
+}
+
+/// How the article in front of an item gets chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Grammar {
+    auto_articles: bool,
+    plurals: Vec<String>, // [RWO] plurals (owned)
+}
+
+impl Grammar {
+    /// Templates as written.
+    const PLAIN: Grammar = Grammar { auto_articles: false, plurals: Vec::new() };
+
+    fn is_plain(&self) -> bool {
+        !self.auto_articles && self.plurals.is_empty()
+    }
+
+    fn is_plural(&self, item: &str) -> bool { // [R] item (borrowed str)
+        self.plurals.iter().any(|plural| plural == item)
+    }
+
+    /// What to put in place of `written`, the template's own `a ` or
+    /// `an `, in front of `item`.
+    fn article<'a>(&self, written: &'a str, item: &str) -> &'a str { // [R] written, item (borrowed str)
+        if self.is_plural(item) {
+            ""
+        } else if !self.auto_articles {
+            written
+        } else if item.starts_with(['a', 'e', 'i', 'o', 'u', 'A', 'E', 'I', 'O', 'U']) {
+            "an "
+        } else {
+            "a "
+        }
+    }
+}
+
+/// `part` split before a trailing `a ` or `an ` that's a word of its own.
+fn article_at_end(part: &str) -> Option<(&str, &str)> { // [R] part (borrowed str)
+    ["an ", "a "].into_iter().find_map(|article| {
+        let before = part.strip_suffix(article)?;
+        (before.is_empty() || before.ends_with(' ')).then(|| part.split_at(before.len()))
+    })
+}
 
-    proverb.push(format!("And all for the want of a {}.", list[0]));
-    proverb.join("\n")
-} // borrow ends: list; drop: proverb
+/// [`build_proverb`] for string literals, done at compile time: the whole
+/// proverb is one `&'static str`.
+///
+/// ```
+/// const PROVERB: &str = proverb::proverb!("nail", "shoe");
+/// assert_eq!(PROVERB, "For want of a nail the shoe was lost.\nAnd all for the want of a nail.");
+/// ```
+#[macro_export]
+macro_rules! proverb {
+    () => {
+        ""
+    };
+    ($first:literal $(, $rest:literal)* $(,)?) => {
+        $crate::proverb!(@lines $first; $first $(, $rest)*;)
+    };
+    (@lines $first:literal; $want:literal, $lost:literal $(, $rest:literal)*; $($done:tt)*) => {
+        $crate::proverb!(@lines $first; $lost $(, $rest)*; $($done)* "For want of a ", $want, " the ", $lost, " was lost.\n",)
+    };
+    (@lines $first:literal; $last:literal; $($done:tt)*) => {
+        concat!($($done)* "And all for the want of a ", $first, ".")
+    };
+}
@@ -2,7 +2,7 @@ use proverb::*;
 
 #[test]
 fn zero_pieces() {
-    let input = &[];
+    let input: &[&str] = &[];
     let output = build_proverb(input);
     let expected = String::new();
     assert_eq!(output, expected);
@@ -73,3 +73,279 @@ fn four_pieces_modernized() {
     .join("\n");
     assert_eq!(output, expected);
 }
+
+#[test]
+fn owned_strings() {
+    let input = vec!["nail".to_string(), "shoe".to_string()];
+    let output = build_proverb(input);
+    let expected: String = [
+        "For want of a nail the shoe was lost.",
+        "And all for the want of a nail.",
+    ]
+    .join("\n");
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn mapped_iterator_of_display_items() {
+    let output = build_proverb((1..=3).map(|n| n * 10));
+    let expected: String = [
+        "For want of a 10 the 20 was lost.",
+        "For want of a 20 the 30 was lost.",
+        "And all for the want of a 10.",
+    ]
+    .join("\n");
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn array_by_value() {
+    assert_eq!(build_proverb(["nail"]), "And all for the want of a nail.");
+    assert_eq!(build_proverb(Vec::<char>::new()), "");
+}
+
+#[test]
+fn builder_defaults_to_the_usual_wording() {
+    let input = ["nail", "shoe", "horse"];
+    assert_eq!(ProverbBuilder::new().build(input), build_proverb(input));
+}
+
+#[test]
+fn builder_with_other_wording() {
+    let builder = ProverbBuilder::new()
+        .line("Faute d'un {}, le {} fut perdu.")
+        .unwrap()
+        .last_line("Et tout cela faute d'un {}.")
+        .unwrap();
+    let output = builder.build(["clou", "fer"]);
+    let expected: String = ["Faute d'un clou, le fer fut perdu.", "Et tout cela faute d'un clou."].join("\n");
+    assert_eq!(output, expected);
+    assert_eq!(builder.build(Vec::<&str>::new()), "");
+}
+
+#[test]
+fn builder_placeholders_can_sit_at_the_ends() {
+    let builder = ProverbBuilder::new().line("{} -> {}").unwrap().last_line("{}").unwrap();
+    assert_eq!(builder.build(["a", "b", "c"]), "a -> b\nb -> c\na");
+}
+
+#[test]
+fn builder_checks_placeholders() {
+    assert_eq!(
+        ProverbBuilder::new().line("For want of a {} all was lost."),
+        Err(TemplateError::Placeholders { expected: 2, found: 1 })
+    );
+    assert_eq!(
+        ProverbBuilder::new().last_line("And that was that."),
+        Err(TemplateError::Placeholders { expected: 1, found: 0 })
+    );
+    assert_eq!(
+        ProverbBuilder::new().last_line("{}{}").unwrap_err().to_string(),
+        "expected 1 {} placeholders, found 2"
+    );
+}
+
+#[test]
+fn lazy_proverb_displays_the_same_text() {
+    let input = ["nail", "shoe", "horse", "rider"];
+    assert_eq!(format!("{}", Proverb::new(&input)), build_proverb(input));
+    assert_eq!(Proverb::new(&["nail"]).to_string(), "And all for the want of a nail.");
+    assert_eq!(Proverb::<&str>::new(&[]).to_string(), "");
+}
+
+#[test]
+fn lazy_proverb_borrows_owned_items() {
+    let input = vec![String::from("pin"), String::from("gun")];
+    let proverb = Proverb::new(&input);
+    assert_eq!(
+        proverb.to_string(),
+        "For want of a pin the gun was lost.\nAnd all for the want of a pin."
+    );
+    assert_eq!(input.len(), 2);
+}
+
+#[test]
+fn write_into_a_string() {
+    let input = ["nail", "shoe", "horse"];
+    let mut out = String::from("> ");
+    write_proverb(input, &mut out).unwrap();
+    assert_eq!(out, format!("> {}", build_proverb(input)));
+}
+
+#[test]
+fn write_streams_owned_items() {
+    let mut out = String::new();
+    write_proverb((1..=3).map(|n| format!("#{n}")), &mut out).unwrap();
+    assert_eq!(out, build_proverb(["#1", "#2", "#3"]));
+    let mut empty = String::new();
+    write_proverb(Vec::<String>::new(), &mut empty).unwrap();
+    assert_eq!(empty, "");
+}
+
+#[test]
+fn write_into_bytes() {
+    let input = ["pin", "gun"];
+    let mut out = Vec::new();
+    write_proverb_io(input, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), build_proverb(input));
+}
+
+#[test]
+fn write_io_errors_come_through() {
+    let mut full = [0u8; 10];
+    let error = write_proverb_io(["nail", "shoe"], &mut full.as_mut_slice()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+}
+
+struct Unprintable;
+
+impl std::fmt::Display for Unprintable {
+    fn fmt(&self, _: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Err(std::fmt::Error)
+    }
+}
+
+#[test]
+fn write_io_reports_display_errors() {
+    let mut out = Vec::new();
+    let error = write_proverb_io([Unprintable, Unprintable], &mut out).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::Other);
+}
+
+#[test]
+fn auto_articles() {
+    let builder = ProverbBuilder::new().auto_articles(true);
+    let output = builder.build(["apple", "cart", "orchard"]);
+    let expected: String = [
+        "For want of an apple the cart was lost.",
+        "For want of a cart the orchard was lost.",
+        "And all for the want of an apple.",
+    ]
+    .join("\n");
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn articles_as_written_by_default() {
+    assert_eq!(build_proverb(["apple"]), "And all for the want of a apple.");
+    let builder = ProverbBuilder::new().line("Lacking an {}, {} went astray.").unwrap();
+    assert_eq!(builder.build(["apple", "pie"]).lines().next(), Some("Lacking an apple, pie went astray."));
+}
+
+#[test]
+fn plural_items_take_no_article() {
+    let builder = ProverbBuilder::new().auto_articles(true).plural(["nails"]);
+    let output = builder.build(["nails", "shoe", "horse"]);
+    let expected: String = [
+        "For want of nails the shoe was lost.",
+        "For want of a shoe the horse was lost.",
+        "And all for the want of nails.",
+    ]
+    .join("\n");
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn plural_items_take_a_plural_verb() {
+    let builder = ProverbBuilder::new().plural(["shoes", "nails"]);
+    let output = builder.build(["nail", "shoes", "horse", "nails"]);
+    let expected: String = [
+        "For want of a nail the shoes were lost.",
+        "For want of shoes the horse was lost.",
+        "For want of a horse the nails were lost.",
+        "And all for the want of a nail.",
+    ]
+    .join("\n");
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn only_a_whole_word_was_agrees() {
+    let builder = ProverbBuilder::new()
+        .line("For want of a {} the {} wasted away.")
+        .unwrap()
+        .plural(["shoes"]);
+    let output = builder.build(["nail", "shoes"]);
+    assert!(output.starts_with("For want of a nail the shoes wasted away."));
+}
+
+#[test]
+fn only_whole_word_articles_change() {
+    let builder = ProverbBuilder::new()
+        .line("Sans a {} no {}.")
+        .unwrap()
+        .last_line("Gotta {}!")
+        .unwrap()
+        .auto_articles(true);
+    assert_eq!(builder.build(["egg", "omelette"]), "Sans an egg no omelette.\nGotta egg!");
+}
+
+#[test]
+fn qualifier_on_the_last_line() {
+    let input = ["nail", "shoe", "horse"];
+    let output = build_proverb_with_qualifier(input, "horseshoe");
+    let expected: String = [
+        "For want of a nail the shoe was lost.",
+        "For want of a shoe the horse was lost.",
+        "And all for the want of a horseshoe nail.",
+    ]
+    .join("\n");
+    assert_eq!(output, expected);
+    assert_eq!(build_proverb_with_qualifier(Vec::<&str>::new(), "horseshoe"), "");
+}
+
+#[test]
+fn qualifier_gets_the_article() {
+    let builder = ProverbBuilder::new().auto_articles(true).qualifier("iron");
+    assert_eq!(builder.build(["nail"]), "And all for the want of an iron nail.");
+}
+
+#[test]
+fn pairs_that_chain() {
+    let output = build_proverb_from_pairs([("nail", "shoe"), ("shoe", "horse")]);
+    assert_eq!(output, build_proverb(["nail", "shoe", "horse"]));
+}
+
+#[test]
+fn pairs_that_do_not_chain() {
+    let output = build_proverb_from_pairs(vec![
+        ("nail".to_string(), "shoe".to_string()),
+        ("sleep".to_string(), "temper".to_string()),
+    ]);
+    let expected: String = [
+        "For want of a nail the shoe was lost.",
+        "For want of a sleep the temper was lost.",
+        "And all for the want of a nail.",
+    ]
+    .join("\n");
+    assert_eq!(output, expected);
+    assert_eq!(build_proverb_from_pairs(Vec::<(&str, &str)>::new()), "");
+}
+
+#[test]
+fn proverb_macro_matches_build_proverb() {
+    const FULL: &str = proverb!("nail", "shoe", "horse", "rider", "message", "battle", "kingdom");
+    assert_eq!(
+        FULL,
+        build_proverb(["nail", "shoe", "horse", "rider", "message", "battle", "kingdom"])
+    );
+    assert_eq!(proverb!("nail"), "And all for the want of a nail.");
+    assert_eq!(proverb!("pin", "gun",), build_proverb(["pin", "gun"]));
+    assert_eq!(proverb!(), "");
+}
+
+#[test]
+fn lines_one_at_a_time() {
+    let input = ["nail", "shoe", "horse"];
+    let lines: Vec<String> = proverb_lines(input).collect();
+    assert_eq!(lines, build_proverb(input).lines().collect::<Vec<_>>());
+    assert_eq!(proverb_lines(["nail"]).collect::<Vec<_>>(), ["And all for the want of a nail."]);
+    assert_eq!(proverb_lines(Vec::<&str>::new()).count(), 0);
+}
+
+#[test]
+fn lines_can_stop_early() {
+    let mut lines = proverb_lines((1..).map(|n| format!("link {n}")));
+    assert_eq!(lines.next().as_deref(), Some("For want of a link 1 the link 2 was lost."));
+    assert_eq!(lines.nth(2).as_deref(), Some("For want of a link 4 the link 5 was lost."));
+}
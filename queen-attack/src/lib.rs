@@ -1,34 +1,900 @@
-#[derive(Debug)]
-pub struct ChessPosition {
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+/// A square on an `N`×`N` board. Rank and file both count from 0.
+///
+/// Squares sort rank by rank, then by file: a1, b1, …, h1, a2 and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChessPositionN<const N: usize> {
     rank: i32,
     file: i32,
 }
 
-#[derive(Debug)]
-pub struct Queen {
-    pos: ChessPosition,
+/// A square on the standard 8×8 board.
+pub type ChessPosition = ChessPositionN<8>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    White,
+    Black,
+}
+
+/// A chess piece, for the geometry of what it attacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Piece {
+    Queen,
+    Rook,
+    Bishop,
+    Knight,
+    King,
+    /// White pawns attack up the board, towards rank 7; black pawns down.
+    Pawn { color: Color },
+}
+
+impl Piece {
+    /// Whether this piece standing on `from` attacks `to`, on an otherwise
+    /// empty board. Nothing attacks its own square.
+    pub fn can_attack<const N: usize>(&self, from: ChessPositionN<N>, to: ChessPositionN<N>) -> bool {
+        if N == 8 {
+            return self.attacks(from.index(), Bitboard::EMPTY).0 & 1 << to.index() != 0;
+        }
+        if from == to {
+            return false;
+        }
+        let delta_file = (to.file - from.file).abs();
+        let delta_rank = to.rank - from.rank;
+        let distance = delta_rank.abs();
+        match self {
+            Piece::Queen => delta_file == 0 || distance == 0 || delta_file == distance,
+            Piece::Rook => delta_file == 0 || distance == 0,
+            Piece::Bishop => delta_file == distance,
+            Piece::Knight => delta_file * distance == 2,
+            Piece::King => delta_file <= 1 && distance <= 1,
+            Piece::Pawn { color } => {
+                let forward = match color {
+                    Color::White => 1,
+                    Color::Black => -1,
+                };
+                delta_file == 1 && delta_rank == forward
+            }
+        }
+    }
+
+    /// Every square this piece on `from` attacks, with the pieces in
+    /// `occupied` stopping queens, rooks and bishops. A blocker's own square
+    /// is attacked, whatever its color.
+    pub fn attacks_from(&self, from: ChessPosition, occupied: Bitboard) -> Bitboard {
+        self.attacks(from.index(), occupied)
+    }
+
+    fn attacks(&self, from: usize, occupied: Bitboard) -> Bitboard {
+        let mask = match self {
+            Piece::Queen => slide(from, 0..8, occupied.0),
+            Piece::Rook => slide(from, (0..8).step_by(2), occupied.0),
+            Piece::Bishop => slide(from, (1..8).step_by(2), occupied.0),
+            Piece::Knight => KNIGHT_ATTACKS[from],
+            Piece::King => KING_ATTACKS[from],
+            Piece::Pawn { color: Color::White } => PAWN_ATTACKS[0][from],
+            Piece::Pawn { color: Color::Black } => PAWN_ATTACKS[1][from],
+        };
+        Bitboard(mask)
+    }
 }
 
-impl ChessPosition {
+/// A queen on a square: [`Piece::Queen`] with somewhere to stand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueenN<const N: usize> {
+    pos: ChessPositionN<N>,
+}
+
+/// A queen on the standard 8×8 board.
+pub type Queen = QueenN<8>;
+
+impl<const N: usize> ChessPositionN<N> {
+    /// `None` off the board; [`TryFrom`] says which coordinate was off.
     pub fn new(rank: i32, file: i32) -> Option<Self> {
-        let range = 0..8;
-        if range.contains(&rank) && range.contains(&file) {
-            Some(Self { rank, file })
+        Self::try_from((rank, file)).ok()
+    }
+
+    pub fn rank(&self) -> i32 {
+        self.rank
+    }
+
+    pub fn file(&self) -> i32 {
+        self.file
+    }
+
+    /// King moves from here to `other`: the larger of the rank and file gaps.
+    pub fn chebyshev_distance(&self, other: &Self) -> u32 {
+        self.rank.abs_diff(other.rank).max(self.file.abs_diff(other.file))
+    }
+
+    /// One-square steps along ranks and files from here to `other`: the two
+    /// gaps added up.
+    pub fn manhattan_distance(&self, other: &Self) -> u32 {
+        self.rank.abs_diff(other.rank) + self.file.abs_diff(other.file)
+    }
+
+    /// Queen moves from here to `other` on an empty board: none to stay put,
+    /// one if they're lined up, and two otherwise, as any two squares share
+    /// a rank with one and a file with the other.
+    pub fn queen_move_distance(&self, other: &Self) -> u32 {
+        if self == other {
+            0
+        } else if Piece::Queen.can_attack(*self, *other) {
+            1
         } else {
-            None
+            2
         }
     }
 }
 
-impl Queen {
-    pub fn new(position: ChessPosition) -> Self {
+/// Why a rank and file aren't a square on the board.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PositionError {
+    RankOutOfRange,
+    FileOutOfRange,
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionError::RankOutOfRange => f.write_str("rank is off the board"),
+            PositionError::FileOutOfRange => f.write_str("file is off the board"),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+/// From `(rank, file)`, the same order as [`ChessPosition::new`].
+impl<const N: usize> TryFrom<(i32, i32)> for ChessPositionN<N> {
+    type Error = PositionError;
+
+    fn try_from((rank, file): (i32, i32)) -> Result<Self, Self::Error> {
+        let range = 0..N as i32;
+        if !range.contains(&rank) {
+            Err(PositionError::RankOutOfRange)
+        } else if !range.contains(&file) {
+            Err(PositionError::FileOutOfRange)
+        } else {
+            Ok(Self { rank, file })
+        }
+    }
+}
+
+/// Why a string isn't a square in algebraic notation.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParsePositionError {
+    /// Squares are exactly two characters, like `e4`.
+    WrongLength,
+    /// The first character wasn't a file from `a` to `h`.
+    InvalidFile(char),
+    /// The second character wasn't a rank from `1` to `8`.
+    InvalidRank(char),
+}
+
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePositionError::WrongLength => f.write_str("a square is a file and a rank, like e4"),
+            ParsePositionError::InvalidFile(c) => write!(f, "no file {c:?}, only a to h"),
+            ParsePositionError::InvalidRank(c) => write!(f, "no rank {c:?}, only 1 to 8"),
+        }
+    }
+}
+
+impl std::error::Error for ParsePositionError {}
+
+/// Algebraic notation: file `a` to `h`, then rank `1` to `8`. Rank and file
+/// 0 are `a1`. Only for the standard board, as that's all it covers.
+impl FromStr for ChessPosition {
+    type Err = ParsePositionError;
+
+    fn from_str(square: &str) -> Result<Self, Self::Err> {
+        let mut chars = square.chars();
+        let (Some(file), Some(rank), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(ParsePositionError::WrongLength);
+        };
+        let file_index = match file {
+            'a'..='h' => file as i32 - 'a' as i32,
+            _ => return Err(ParsePositionError::InvalidFile(file)),
+        };
+        let rank_index = match rank {
+            '1'..='8' => rank as i32 - '1' as i32,
+            _ => return Err(ParsePositionError::InvalidRank(rank)),
+        };
+        Ok(Self {
+            rank: rank_index,
+            file: file_index,
+        })
+    }
+}
+
+impl fmt::Display for ChessPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = char::from(b'a' + self.file as u8);
+        write!(f, "{file}{}", self.rank + 1)
+    }
+}
+
+impl<const N: usize> QueenN<N> {
+    pub fn new(position: ChessPositionN<N>) -> Self {
         Self { pos: position }
     }
 
-    pub fn can_attack(&self, other: &Queen) -> bool {
-        let delta_x = (self.pos.file - other.pos.file).abs();
-        let delta_y = (self.pos.rank - other.pos.rank).abs();
-        delta_x == 0 || delta_y == 0 || delta_x == delta_y
+    pub fn can_attack(&self, other: &QueenN<N>) -> bool {
+        Piece::Queen.can_attack(self.pos, other.pos)
+    }
+
+    /// The squares between the two queens, in order from this one, if they
+    /// attack each other: empty when they're next to each other, `None`
+    /// when they aren't lined up.
+    pub fn attack_path(&self, other: &QueenN<N>) -> Option<Vec<ChessPositionN<N>>> {
+        if !self.can_attack(other) {
+            return None;
+        }
+        Some(self.pos.between(other.pos)?.collect())
+    }
+
+    /// Every square along this queen's rank, file and diagonals, ray by
+    /// ray, nearest first.
+    pub fn attacked_squares(&self) -> impl Iterator<Item = ChessPositionN<N>> + '_ {
+        let bitboard = (N == 8).then(|| {
+            ray_squares(self.pos.index(), 0).map(ChessPositionN::from_index)
+        });
+        let stepped = (N != 8).then(|| rays(self.pos, &QUEEN_DIRECTIONS, |_| false));
+        bitboard.into_iter().flatten().chain(stepped.into_iter().flatten())
+    }
+}
+
+impl Queen {
+    /// [`attacked_squares`](Self::attacked_squares), with each ray stopping
+    /// at the first piece on `board`. That piece's square is included: it's
+    /// attacked too, it just hides everything behind it.
+    pub fn attacked_squares_on<'a>(
+        &'a self,
+        board: &'a Board,
+    ) -> impl Iterator<Item = ChessPosition> + 'a {
+        ray_squares(self.pos.index(), board.occupied.0).map(ChessPosition::from_index)
+    }
+
+    /// Where this queen can move on `board`: [`attacked_squares_on`], less
+    /// any square holding a piece of its own color. Enemy pieces can be
+    /// taken. Its color is whatever's standing on its square; if that's
+    /// empty, every piece counts as an enemy.
+    ///
+    /// [`attacked_squares_on`]: Self::attacked_squares_on
+    pub fn moves<'a>(&'a self, board: &'a Board) -> impl Iterator<Item = ChessPosition> + 'a {
+        let own = board.get(self.pos).map(|(color, _)| color);
+        self.attacked_squares_on(board)
+            .filter(move |&square| board.get(square).is_none_or(|(color, _)| Some(color) != own))
+    }
+}
+
+/// Rank and file steps along a queen's eight rays.
+const QUEEN_DIRECTIONS: [(i32, i32); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// The squares out from `from` along each of `directions`, up to the edge
+/// of the board or up to and including the first square `is_blocked`. The
+/// standard board has [`ray_squares`] instead.
+fn rays<'a, const N: usize>(
+    from: ChessPositionN<N>,
+    directions: &'a [(i32, i32)],
+    is_blocked: impl Fn(ChessPositionN<N>) -> bool + Copy + 'a,
+) -> impl Iterator<Item = ChessPositionN<N>> + 'a {
+    directions.iter().flat_map(move |&(rank, file)| {
+        std::iter::successors(from.offset(rank, file), move |&square| {
+            if is_blocked(square) { None } else { square.offset(rank, file) }
+        })
+    })
+}
+
+/// `Qd4`, as in a move list.
+impl fmt::Display for Queen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Q{}", self.pos)
+    }
+}
+
+impl<const N: usize> ChessPositionN<N> {
+    /// The bit for this square in a [`Bitboard`]. Only meaningful when `N`
+    /// is 8.
+    const fn index(self) -> usize {
+        (self.rank * 8 + self.file) as usize
+    }
+
+    const fn from_index(index: usize) -> Self {
+        Self {
+            rank: (index / 8) as i32,
+            file: (index % 8) as i32,
+        }
+    }
+
+    /// The square `rank` and `file` steps away, if that's still on the board.
+    fn offset(self, rank: i32, file: i32) -> Option<Self> {
+        Self::new(self.rank + rank, self.file + file)
+    }
+
+    /// The squares strictly between `self` and `to`, walking from `self`,
+    /// if they share a rank, file or diagonal. Empty when they're adjacent
+    /// or the same square; `None` when they aren't lined up at all.
+    fn between(self, to: Self) -> Option<impl Iterator<Item = Self>> {
+        let delta_rank = to.rank - self.rank;
+        let delta_file = to.file - self.file;
+        let aligned = delta_rank == 0 || delta_file == 0 || delta_rank.abs() == delta_file.abs();
+        aligned.then(|| {
+            let steps = delta_rank.abs().max(delta_file.abs());
+            let (step_rank, step_file) = (delta_rank.signum(), delta_file.signum());
+            (1..steps).map(move |i| Self {
+                rank: self.rank + i * step_rank,
+                file: self.file + i * step_file,
+            })
+        })
+    }
+}
+
+/// A set of squares on the standard board, one bit each: a1 is bit 0, b1
+/// bit 1, and so on up to h8 as bit 63.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+
+    pub const fn contains(self, square: ChessPosition) -> bool {
+        self.0 & 1 << square.index() != 0
+    }
+
+    pub fn insert(&mut self, square: ChessPosition) {
+        self.0 |= 1 << square.index();
+    }
+
+    pub fn remove(&mut self, square: ChessPosition) {
+        self.0 &= !(1 << square.index());
+    }
+
+    pub const fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The squares in the set, a1 first, rank by rank.
+    pub fn squares(self) -> impl Iterator<Item = ChessPosition> {
+        bits(self.0, false).map(ChessPosition::from_index)
+    }
+}
+
+impl FromIterator<ChessPosition> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = ChessPosition>>(squares: I) -> Self {
+        let mut bitboard = Bitboard::EMPTY;
+        for square in squares {
+            bitboard.insert(square);
+        }
+        bitboard
+    }
+}
+
+/// The set bits of `mask`, lowest first or, if `descending`, highest first.
+fn bits(mut mask: u64, descending: bool) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        (mask != 0).then(|| {
+            let index = if descending { 63 - mask.leading_zeros() } else { mask.trailing_zeros() };
+            mask &= !(1 << index);
+            index as usize
+        })
+    })
+}
+
+/// Whether walking along `QUEEN_DIRECTIONS[direction]` goes up the bits.
+const fn ascending(direction: usize) -> bool {
+    let (rank, file) = QUEEN_DIRECTIONS[direction];
+    rank > 0 || (rank == 0 && file > 0)
+}
+
+/// `RAYS[direction][square]`: everything out from `square` to the edge of an
+/// empty board, for each of `QUEEN_DIRECTIONS`.
+const RAYS: [[u64; 64]; 8] = {
+    let mut rays = [[0; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let (step_rank, step_file) = QUEEN_DIRECTIONS[direction];
+        let mut square = 0;
+        while square < 64 {
+            let (mut rank, mut file) = (square as i32 / 8 + step_rank, square as i32 % 8 + step_file);
+            while rank >= 0 && rank < 8 && file >= 0 && file < 8 {
+                rays[direction][square] |= 1 << (rank * 8 + file);
+                rank += step_rank;
+                file += step_file;
+            }
+            square += 1;
+        }
+        direction += 1;
+    }
+    rays
+};
+
+/// For each square, the squares one of `steps` away from it.
+const fn leaper_attacks(steps: &[(i32, i32)]) -> [u64; 64] {
+    let mut attacks = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let mut i = 0;
+        while i < steps.len() {
+            let (rank, file) = (square as i32 / 8 + steps[i].0, square as i32 % 8 + steps[i].1);
+            if rank >= 0 && rank < 8 && file >= 0 && file < 8 {
+                attacks[square] |= 1 << (rank * 8 + file);
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+    attacks
+}
+
+const KNIGHT_ATTACKS: [u64; 64] =
+    leaper_attacks(&[(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)]);
+const KING_ATTACKS: [u64; 64] = leaper_attacks(&QUEEN_DIRECTIONS);
+/// White pawns first, then black.
+const PAWN_ATTACKS: [[u64; 64]; 2] = [leaper_attacks(&[(1, -1), (1, 1)]), leaper_attacks(&[(-1, -1), (-1, 1)])];
+
+/// The ray out from `from` along `QUEEN_DIRECTIONS[direction]`, cut off
+/// after its first square in `occupied`.
+fn ray(from: usize, direction: usize, occupied: u64) -> u64 {
+    let ray = RAYS[direction][from];
+    let blockers = ray & occupied;
+    if blockers == 0 {
+        return ray;
+    }
+    let nearest = if ascending(direction) { blockers.trailing_zeros() } else { 63 - blockers.leading_zeros() };
+    ray & !RAYS[direction][nearest as usize]
+}
+
+fn slide(from: usize, directions: impl Iterator<Item = usize>, occupied: u64) -> u64 {
+    directions.fold(0, |mask, direction| mask | ray(from, direction, occupied))
+}
+
+/// A queen's squares on the standard board, ray by ray in `QUEEN_DIRECTIONS`
+/// order, nearest first, each stopping at the first square in `occupied`.
+fn ray_squares(from: usize, occupied: u64) -> impl Iterator<Item = usize> {
+    (0..8).flat_map(move |direction| bits(ray(from, direction, occupied), !ascending(direction)))
+}
+
+/// Pieces on a standard board, where they can get in each other's way.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Board {
+    squares: [[Option<(Color, Piece)>; 8]; 8],
+    occupied: Bitboard,
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put `color`'s `piece` on `at`, handing back whatever was there. A
+    /// pawn of the other color is an error, and leaves the board as it was.
+    pub fn place(
+        &mut self,
+        at: ChessPosition,
+        color: Color,
+        piece: Piece,
+    ) -> Result<Option<(Color, Piece)>, PlacementError> {
+        if let Piece::Pawn { color: pawn } = piece
+            && pawn != color
+        {
+            return Err(PlacementError::PawnColorMismatch { color, pawn });
+        }
+        self.occupied.insert(at);
+        Ok(self.square_mut(at).replace((color, piece)))
+    }
+
+    pub fn remove(&mut self, at: ChessPosition) -> Option<(Color, Piece)> {
+        self.occupied.remove(at);
+        self.square_mut(at).take()
+    }
+
+    /// Every square with a piece on it.
+    pub fn occupied(&self) -> Bitboard {
+        self.occupied
+    }
+
+    /// What the piece on `from` attacks, blockers and all; empty if there's
+    /// no piece there.
+    pub fn attacks_from(&self, from: ChessPosition) -> Bitboard {
+        match self.get(from) {
+            Some((_, piece)) => piece.attacks_from(from, self.occupied),
+            None => Bitboard::EMPTY,
+        }
+    }
+
+    pub fn get(&self, at: ChessPosition) -> Option<(Color, Piece)> {
+        self.squares[at.rank as usize][at.file as usize]
+    }
+
+    fn square_mut(&mut self, at: ChessPosition) -> &mut Option<(Color, Piece)> {
+        &mut self.squares[at.rank as usize][at.file as usize]
+    }
+
+    /// Every occupied square with what's on it, rank by rank from a1.
+    pub fn pieces(&self) -> impl Iterator<Item = (ChessPosition, Color, Piece)> + '_ {
+        (0..8).flat_map(move |rank| {
+            (0..8).filter_map(move |file| {
+                let (color, piece) = self.squares[rank as usize][file as usize]?;
+                Some((ChessPosition { rank, file }, color, piece))
+            })
+        })
+    }
+
+    /// Whether the piece on `from` attacks `to`. Queens, rooks and bishops
+    /// can't see past a piece in the way; knights jump, and kings and pawns
+    /// only reach next door anyway. False if `from` is empty.
+    pub fn can_attack(&self, from: ChessPosition, to: ChessPosition) -> bool {
+        self.attacks_from(from).contains(to)
+    }
+
+    /// The squares of every piece, of either color, attacking `square`.
+    pub fn attackers_of(&self, square: ChessPosition) -> Vec<ChessPosition> {
+        self.pieces()
+            .map(|(from, _, _)| from)
+            .filter(|&from| self.can_attack(from, square))
+            .collect()
+    }
+
+    /// How many pieces, of either color, attack each square, indexed
+    /// `[rank][file]` like the board itself. Blockers count the same as in
+    /// [`can_attack`](Self::can_attack).
+    pub fn coverage(&self) -> [[u8; 8]; 8] {
+        let mut counts = [[0; 8]; 8];
+        for (from, _, piece) in self.pieces() {
+            for to in piece.attacks_from(from, self.occupied).squares() {
+                counts[to.rank as usize][to.file as usize] += 1;
+            }
+        }
+        counts
+    }
+}
+
+impl Board {
+    /// A diagram of the board, rank 8 at the top, with a chess glyph for
+    /// each piece and `·` for an empty square. Same as [`Display`](fmt::Display).
+    pub fn render(&self) -> String {
+        self.render_marked(std::iter::empty())
+    }
+
+    /// [`render`](Self::render), with the empty squares among `marked`
+    /// drawn as `×` instead. Hand it [`Queen::attacked_squares_on`] and the
+    /// like to show what's under attack.
+    pub fn render_marked(&self, marked: impl IntoIterator<Item = ChessPosition>) -> String {
+        let mut marks = [[false; 8]; 8];
+        for square in marked {
+            marks[square.rank as usize][square.file as usize] = true;
+        }
+        let mut out = String::new();
+        for rank in (0..8).rev() {
+            out.push_str(&(rank + 1).to_string());
+            for (square, marked) in self.squares[rank].iter().zip(marks[rank]) {
+                out.push(' ');
+                out.push(match *square {
+                    Some((color, piece)) => glyph(color, piece),
+                    None if marked => '×',
+                    None => '·',
+                });
+            }
+            out.push('\n');
+        }
+        out.push_str("  a b c d e f g h");
+        out
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+fn glyph(color: Color, piece: Piece) -> char {
+    match (color, piece) {
+        (Color::White, Piece::King) => '♔',
+        (Color::White, Piece::Queen) => '♕',
+        (Color::White, Piece::Rook) => '♖',
+        (Color::White, Piece::Bishop) => '♗',
+        (Color::White, Piece::Knight) => '♘',
+        (Color::White, Piece::Pawn { .. }) => '♙',
+        (Color::Black, Piece::King) => '♚',
+        (Color::Black, Piece::Queen) => '♛',
+        (Color::Black, Piece::Rook) => '♜',
+        (Color::Black, Piece::Bishop) => '♝',
+        (Color::Black, Piece::Knight) => '♞',
+        (Color::Black, Piece::Pawn { .. }) => '♟',
+    }
+}
+
+/// Why a piece can't go on the board.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PlacementError {
+    /// The pawn's own color isn't the one it was placed as.
+    PawnColorMismatch { color: Color, pawn: Color },
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementError::PawnColorMismatch { color, pawn } => {
+                write!(f, "a {color:?} piece can't be a {pawn:?} pawn")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlacementError {}
+
+/// Why a string isn't a FEN piece placement.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FenError {
+    /// Placements have eight ranks split by `/`; this one had some other count.
+    WrongRankCount(usize),
+    /// The rank, counting from 8 down like FEN does, didn't cover exactly
+    /// eight squares.
+    WrongRankLength(usize),
+    /// Not a piece letter or a run of empty squares.
+    InvalidPiece(char),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FenError::WrongRankCount(n) => write!(f, "expected 8 ranks, found {n}"),
+            FenError::WrongRankLength(rank) => write!(f, "rank {rank} isn't 8 squares long"),
+            FenError::InvalidPiece(c) => write!(f, "no piece {c:?}"),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+impl Board {
+    /// The board from a FEN string. Only the piece placement matters here,
+    /// so whatever follows it (side to move, castling and so on) is ignored.
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let placement = fen.split_whitespace().next().unwrap_or_default();
+        let ranks: Vec<&str> = placement.split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::WrongRankCount(ranks.len()));
+        }
+        let mut board = Board::new();
+        for (row, pieces) in ranks.into_iter().enumerate() {
+            let rank = 7 - row as i32;
+            let mut file = 0;
+            for c in pieces.chars() {
+                if let Some(empty) = c.to_digit(10).filter(|n| (1..=8).contains(n)) {
+                    file += empty as i32;
+                    continue;
+                }
+                let (color, piece) = fen_piece(c).ok_or(FenError::InvalidPiece(c))?;
+                let at = ChessPosition::new(rank, file).ok_or(FenError::WrongRankLength(rank as usize + 1))?;
+                board.place(at, color, piece).expect("FEN letters give pawns their own color");
+                file += 1;
+            }
+            if file != 8 {
+                return Err(FenError::WrongRankLength(rank as usize + 1));
+            }
+        }
+        Ok(board)
+    }
+
+    /// The FEN piece placement, rank 8 first. Just that field: a board has
+    /// no idea whose move it is.
+    pub fn to_fen(&self) -> String {
+        let ranks: Vec<String> = (0..8)
+            .rev()
+            .map(|rank| {
+                let mut out = String::new();
+                let mut empty = 0;
+                for square in &self.squares[rank] {
+                    match square {
+                        Some((color, piece)) => {
+                            if empty > 0 {
+                                out.push_str(&empty.to_string());
+                                empty = 0;
+                            }
+                            out.push(fen_letter(*color, *piece));
+                        }
+                        None => empty += 1,
+                    }
+                }
+                if empty > 0 {
+                    out.push_str(&empty.to_string());
+                }
+                out
+            })
+            .collect();
+        ranks.join("/")
+    }
+}
+
+/// FEN letters: upper case for white, lower case for black.
+fn fen_piece(c: char) -> Option<(Color, Piece)> {
+    let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+    let piece = match c.to_ascii_lowercase() {
+        'q' => Piece::Queen,
+        'r' => Piece::Rook,
+        'b' => Piece::Bishop,
+        'n' => Piece::Knight,
+        'k' => Piece::King,
+        'p' => Piece::Pawn { color },
+        _ => return None,
+    };
+    Some((color, piece))
+}
 
+fn fen_letter(color: Color, piece: Piece) -> char {
+    let letter = match piece {
+        Piece::Queen => 'q',
+        Piece::Rook => 'r',
+        Piece::Bishop => 'b',
+        Piece::Knight => 'n',
+        Piece::King => 'k',
+        Piece::Pawn { .. } => 'p',
+    };
+    match color {
+        Color::White => letter.to_ascii_uppercase(),
+        Color::Black => letter,
+    }
+}
+
+/// Every way to put `N` queens on an `N`×`N` board with none attacking
+/// another, one queen per rank from rank 0 up. `n_queens::<8>()` gives the
+/// 92 classic solutions.
+///
+/// Files, and both diagonal directions, are tracked as bit masks, so `N`
+/// goes up to 64.
+pub fn n_queens<const N: usize>() -> NQueens<N> {
+    const { assert!(N <= 64, "n_queens tracks files in a u64") };
+    let full = if N == 64 { u64::MAX } else { (1 << N) - 1 };
+    NQueens {
+        full,
+        stack: vec![Frame { files: 0, left: 0, right: 0, free: full }],
+        placed: Vec::with_capacity(N),
+    }
+}
+
+/// Iterator from [`n_queens`].
+#[derive(Debug, Clone)]
+pub struct NQueens<const N: usize> {
+    full: u64,
+    /// One frame per rank being tried, with the files still to try there.
+    stack: Vec<Frame>,
+    /// The file chosen on each rank below the top of the stack.
+    placed: Vec<i32>,
+}
+
+/// Files taken by a queen below, and by a diagonal running up from one to
+/// the left or right, as seen from this rank.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    files: u64,
+    left: u64,
+    right: u64,
+    free: u64,
+}
+
+impl<const N: usize> Iterator for NQueens<N> {
+    type Item = Vec<ChessPositionN<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(frame) = self.stack.last_mut() {
+            if frame.free == 0 {
+                self.stack.pop();
+                continue;
+            }
+            let bit = frame.free & frame.free.wrapping_neg();
+            frame.free ^= bit;
+            let Frame { files, left, right, .. } = *frame;
+
+            let rank = self.stack.len() - 1;
+            self.placed.truncate(rank);
+            self.placed.push(bit.trailing_zeros() as i32);
+            if rank + 1 == N {
+                return Some(
+                    self.placed
+                        .iter()
+                        .zip(0..)
+                        .map(|(&file, rank)| ChessPositionN { rank, file })
+                        .collect(),
+                );
+            }
+
+            let (files, left, right) = (files | bit, ((left | bit) << 1) & self.full, (right | bit) >> 1);
+            let free = self.full & !(files | left | right);
+            self.stack.push(Frame { files, left, right, free });
+        }
+        None
+    }
+}
+
+/// The empty squares none of `queens` attacks, rank by rank from a1.
+pub fn safe_squares(queens: &[Queen]) -> Vec<ChessPosition> {
+    let mut board = Board::new();
+    for queen in queens {
+        board.place(queen.pos, Color::White, Piece::Queen).expect("queens have no color of their own");
+    }
+    let coverage = board.coverage();
+    (0..8)
+        .flat_map(|rank| (0..8).map(move |file| ChessPosition { rank, file }))
+        .filter(|&square| {
+            board.get(square).is_none() && coverage[square.rank as usize][square.file as usize] == 0
+        })
+        .collect()
+}
+
+/// Every pair of `queens`, by index with the smaller first, that attack
+/// each other, in order. As with [`QueenN::can_attack`], nothing gets in
+/// the way, so a count of zero means a peaceful placement.
+///
+/// Queens are grouped by the rank, file and diagonals they sit on, so only
+/// queens that share a line are ever compared.
+pub fn attacking_pairs<const N: usize>(queens: &[QueenN<N>]) -> Vec<(usize, usize)> {
+    let mut lines: HashMap<(u8, i32), Vec<usize>> = HashMap::new();
+    for (i, queen) in queens.iter().enumerate() {
+        let ChessPositionN { rank, file } = queen.pos;
+        for line in [(0, rank), (1, file), (2, rank - file), (3, rank + file)] {
+            lines.entry(line).or_default().push(i);
+        }
+    }
+    let mut pairs: Vec<(usize, usize)> = lines
+        .values()
+        .flat_map(|line| {
+            line.iter().enumerate().flat_map(move |(n, &i)| {
+                line[n + 1..]
+                    .iter()
+                    .filter(move |&&j| queens[i].pos != queens[j].pos)
+                    .map(move |&j| (i, j))
+            })
+        })
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+/// The fewest queen moves from `from` to `to` without landing on or passing
+/// through any piece on `blocked`, as the squares landed on, starting with
+/// `from` and ending with `to`. `None` if `to` can't be reached.
+pub fn shortest_route(from: ChessPosition, to: ChessPosition, blocked: &Board) -> Option<Vec<ChessPosition>> {
+    let mut came_from: [Option<ChessPosition>; 64] = [None; 64];
+    came_from[from.index()] = Some(from);
+    let mut queue = VecDeque::from([from]);
+    while let Some(square) = queue.pop_front() {
+        if square == to {
+            let mut route = vec![to];
+            let mut at = to;
+            while at != from {
+                at = came_from[at.index()]?;
+                route.push(at);
+            }
+            route.reverse();
+            return Some(route);
+        }
+        for next in Queen::new(square).attacked_squares_on(blocked) {
+            if blocked.get(next).is_none() && came_from[next.index()].is_none() {
+                came_from[next.index()] = Some(square);
+                queue.push_back(next);
+            }
+        }
     }
+    None
 }
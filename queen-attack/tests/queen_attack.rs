@@ -86,3 +86,598 @@ fn cannot_attack_if_falling_diagonals_are_only_the_same_when_reflected_across_th
     let black_queen = Queen::new(ChessPosition::new(2, 5).unwrap());
     assert!(!white_queen.can_attack(&black_queen));
 }
+
+#[test]
+fn parse_algebraic_notation() {
+    assert_eq!("a1".parse(), Ok(ChessPosition::new(0, 0).unwrap()));
+    assert_eq!("h8".parse(), Ok(ChessPosition::new(7, 7).unwrap()));
+    assert_eq!("e4".parse(), Ok(ChessPosition::new(3, 4).unwrap()));
+}
+
+#[test]
+fn parse_rejects_off_board_squares() {
+    assert_eq!("i1".parse::<ChessPosition>(), Err(ParsePositionError::InvalidFile('i')));
+    assert_eq!("a9".parse::<ChessPosition>(), Err(ParsePositionError::InvalidRank('9')));
+    assert_eq!("a0".parse::<ChessPosition>(), Err(ParsePositionError::InvalidRank('0')));
+    assert_eq!("A1".parse::<ChessPosition>(), Err(ParsePositionError::InvalidFile('A')));
+    assert_eq!("a10".parse::<ChessPosition>(), Err(ParsePositionError::WrongLength));
+    assert_eq!("".parse::<ChessPosition>(), Err(ParsePositionError::WrongLength));
+}
+
+#[test]
+fn display_round_trips() {
+    for rank in 0..8 {
+        for file in 0..8 {
+            let position = ChessPosition::new(rank, file).unwrap();
+            assert_eq!(position.to_string().parse(), Ok(position));
+        }
+    }
+    assert_eq!(ChessPosition::new(3, 4).unwrap().to_string(), "e4");
+}
+
+#[test]
+fn display_queen() {
+    let queen = Queen::new("d4".parse().unwrap());
+    assert_eq!(queen.to_string(), "Qd4");
+}
+
+#[test]
+fn try_from_says_what_was_wrong() {
+    assert_eq!(ChessPosition::try_from((2, 3)), Ok(ChessPosition::new(2, 3).unwrap()));
+    assert_eq!(ChessPosition::try_from((-2, 2)), Err(PositionError::RankOutOfRange));
+    assert_eq!(ChessPosition::try_from((8, 4)), Err(PositionError::RankOutOfRange));
+    assert_eq!(ChessPosition::try_from((2, -2)), Err(PositionError::FileOutOfRange));
+    assert_eq!(ChessPosition::try_from((4, 8)), Err(PositionError::FileOutOfRange));
+    // Rank gets checked first.
+    assert_eq!(ChessPosition::try_from((8, 8)), Err(PositionError::RankOutOfRange));
+}
+
+fn square(name: &str) -> ChessPosition {
+    name.parse().unwrap()
+}
+
+#[test]
+fn piece_geometry() {
+    let d4 = square("d4");
+    let attacks = |piece: Piece, to: &str| piece.can_attack(d4, square(to));
+
+    assert!(attacks(Piece::Rook, "d8") && attacks(Piece::Rook, "a4"));
+    assert!(!attacks(Piece::Rook, "e5"));
+    assert!(attacks(Piece::Bishop, "a1") && attacks(Piece::Bishop, "g7"));
+    assert!(!attacks(Piece::Bishop, "d5"));
+    assert!(attacks(Piece::Knight, "e6") && attacks(Piece::Knight, "b3"));
+    assert!(!attacks(Piece::Knight, "d6") && !attacks(Piece::Knight, "f6"));
+    assert!(attacks(Piece::King, "e5") && attacks(Piece::King, "c4"));
+    assert!(!attacks(Piece::King, "d6"));
+    assert!(attacks(Piece::Queen, "h8") && attacks(Piece::Queen, "d1"));
+    assert!(!attacks(Piece::Queen, "e6"));
+}
+
+#[test]
+fn pawns_attack_forward_diagonally() {
+    let white = Piece::Pawn { color: Color::White };
+    let black = Piece::Pawn { color: Color::Black };
+    let d4 = square("d4");
+
+    assert!(white.can_attack(d4, square("c5")) && white.can_attack(d4, square("e5")));
+    assert!(!white.can_attack(d4, square("d5")) && !white.can_attack(d4, square("e3")));
+    assert!(black.can_attack(d4, square("c3")) && black.can_attack(d4, square("e3")));
+    assert!(!black.can_attack(d4, square("e5")));
+}
+
+#[test]
+fn nothing_attacks_its_own_square() {
+    let pieces = [
+        Piece::Queen,
+        Piece::Rook,
+        Piece::Bishop,
+        Piece::Knight,
+        Piece::King,
+        Piece::Pawn { color: Color::White },
+    ];
+    for piece in pieces {
+        assert!(!piece.can_attack(square("d4"), square("d4")), "{piece:?}");
+    }
+}
+
+#[test]
+fn board_place_and_remove() {
+    let mut board = Board::new();
+    assert_eq!(board.place(square("d4"), Color::White, Piece::Queen), Ok(None));
+    assert_eq!(
+        board.place(square("d4"), Color::Black, Piece::Rook),
+        Ok(Some((Color::White, Piece::Queen)))
+    );
+    assert_eq!(board.get(square("d4")), Some((Color::Black, Piece::Rook)));
+    assert_eq!(board.remove(square("d4")), Some((Color::Black, Piece::Rook)));
+    assert_eq!(board.get(square("d4")), None);
+    assert_eq!(board.pieces().count(), 0);
+}
+
+#[test]
+fn blocked_queen_cannot_attack_through_a_piece() {
+    let mut board = Board::new();
+    board.place(square("a1"), Color::White, Piece::Queen).unwrap();
+    board.place(square("h8"), Color::Black, Piece::Queen).unwrap();
+    assert!(board.can_attack(square("a1"), square("h8")));
+
+    board.place(square("d4"), Color::White, Piece::Pawn { color: Color::White }).unwrap();
+    assert!(!board.can_attack(square("a1"), square("h8")));
+    assert!(!board.can_attack(square("h8"), square("a1")));
+    // The blocker itself is still in reach.
+    assert!(board.can_attack(square("a1"), square("d4")));
+    assert!(board.can_attack(square("h8"), square("d4")));
+}
+
+#[test]
+fn knights_jump_over_blockers() {
+    let mut board = Board::new();
+    board.place(square("b1"), Color::White, Piece::Knight).unwrap();
+    board.place(square("b2"), Color::White, Piece::Rook).unwrap();
+    board.place(square("c2"), Color::White, Piece::Rook).unwrap();
+    assert!(board.can_attack(square("b1"), square("c3")));
+    // Nothing on c4 to do any attacking.
+    assert!(!board.can_attack(square("c4"), square("c3")));
+}
+
+#[test]
+fn attackers_of_a_square() {
+    let mut board = Board::new();
+    board.place(square("e1"), Color::White, Piece::Rook).unwrap();
+    board.place(square("a4"), Color::Black, Piece::Bishop).unwrap();
+    board.place(square("f3"), Color::White, Piece::Knight).unwrap();
+    board.place(square("d4"), Color::Black, Piece::Pawn { color: Color::Black }).unwrap();
+    board.place(square("h5"), Color::Black, Piece::Queen).unwrap();
+    board.place(square("g6"), Color::White, Piece::King).unwrap();
+
+    // Rook up the file, knight, queen along the rank; the bishop and king
+    // aren't lined up and the black pawn faces the other way.
+    let attackers = board.attackers_of(square("e5"));
+    assert_eq!(attackers, [square("e1"), square("f3"), square("h5")]);
+    assert_eq!(board.attackers_of(square("e3")), [square("e1"), square("d4")]);
+}
+
+#[test]
+fn pawn_color_must_match() {
+    let mut board = Board::new();
+    assert_eq!(
+        board.place(square("e2"), Color::Black, Piece::Pawn { color: Color::White }),
+        Err(PlacementError::PawnColorMismatch {
+            color: Color::Black,
+            pawn: Color::White
+        })
+    );
+    assert_eq!(board.get(square("e2")), None);
+    assert!(board.occupied().is_empty());
+}
+
+#[test]
+fn queen_attacked_squares_on_an_empty_board() {
+    let corner = Queen::new(square("a1"));
+    assert_eq!(corner.attacked_squares().count(), 21);
+
+    let center = Queen::new(square("d4"));
+    let attacked: Vec<ChessPosition> = center.attacked_squares().collect();
+    assert_eq!(attacked.len(), 27);
+    assert!(!attacked.contains(&square("d4")));
+    for to in &attacked {
+        assert!(center.can_attack(&Queen::new(*to)), "{to}");
+    }
+}
+
+#[test]
+fn queen_attacked_squares_stop_at_blockers() {
+    let mut board = Board::new();
+    board.place(square("d6"), Color::Black, Piece::Knight).unwrap();
+    board.place(square("f6"), Color::White, Piece::Rook).unwrap();
+    let queen = Queen::new(square("d4"));
+
+    let attacked: Vec<ChessPosition> = queen.attacked_squares_on(&board).collect();
+    assert!(attacked.contains(&square("d5")) && attacked.contains(&square("d6")));
+    assert!(!attacked.contains(&square("d7")) && !attacked.contains(&square("d8")));
+    assert!(attacked.contains(&square("f6")) && !attacked.contains(&square("g7")));
+    assert_eq!(attacked.len(), 27 - 2 - 2);
+}
+
+#[test]
+fn attack_path_lists_the_squares_in_between() {
+    let queen = Queen::new(square("c1"));
+    assert_eq!(
+        queen.attack_path(&Queen::new(square("f4"))),
+        Some(vec![square("d2"), square("e3")])
+    );
+    assert_eq!(
+        Queen::new(square("f4")).attack_path(&queen),
+        Some(vec![square("e3"), square("d2")])
+    );
+    assert_eq!(
+        queen.attack_path(&Queen::new(square("c4"))),
+        Some(vec![square("c2"), square("c3")])
+    );
+}
+
+#[test]
+fn attack_path_for_neighbours_and_strangers() {
+    let queen = Queen::new(square("c1"));
+    assert_eq!(queen.attack_path(&Queen::new(square("d2"))), Some(vec![]));
+    assert_eq!(queen.attack_path(&Queen::new(square("d3"))), None);
+    assert_eq!(queen.attack_path(&queen), None);
+}
+
+#[test]
+fn bigger_boards() {
+    assert!(ChessPositionN::<10>::new(9, 9).is_some());
+    assert!(ChessPositionN::<10>::new(10, 0).is_none());
+    assert_eq!(
+        ChessPositionN::<10>::try_from((3, 10)),
+        Err(PositionError::FileOutOfRange)
+    );
+
+    let corner = QueenN::new(ChessPositionN::<10>::new(0, 0).unwrap());
+    let far_corner = QueenN::new(ChessPositionN::<10>::new(9, 9).unwrap());
+    assert!(corner.can_attack(&far_corner));
+    assert_eq!(corner.attack_path(&far_corner).map(|path| path.len()), Some(8));
+    assert_eq!(corner.attacked_squares().count(), 27);
+}
+
+#[test]
+fn smaller_boards() {
+    assert!(ChessPositionN::<4>::new(3, 3).is_some());
+    assert!(ChessPositionN::<4>::new(4, 3).is_none());
+    let queen = QueenN::new(ChessPositionN::<4>::new(1, 1).unwrap());
+    assert_eq!(queen.attacked_squares().count(), 11);
+    assert!(Piece::Knight.can_attack(
+        ChessPositionN::<4>::new(0, 0).unwrap(),
+        ChessPositionN::<4>::new(2, 1).unwrap()
+    ));
+}
+
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[test]
+fn fen_round_trip() {
+    let board = Board::from_fen(START).unwrap();
+    assert_eq!(board.get(square("d1")), Some((Color::White, Piece::Queen)));
+    assert_eq!(board.get(square("g8")), Some((Color::Black, Piece::Knight)));
+    assert_eq!(
+        board.get(square("e7")),
+        Some((Color::Black, Piece::Pawn { color: Color::Black }))
+    );
+    assert_eq!(board.get(square("e4")), None);
+    assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR");
+
+    let middlegame = "r1bq1rk1/ppp2ppp/2n2n2/3pp3/1bPP4/2N1PN2/PP3PPP/R1BQKB1R";
+    assert_eq!(Board::from_fen(middlegame).unwrap().to_fen(), middlegame);
+    assert_eq!(Board::new().to_fen(), "8/8/8/8/8/8/8/8");
+}
+
+#[test]
+fn fen_attacks_on_a_real_position() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4Q2K w - - 0 1").unwrap();
+    assert!(!board.can_attack(square("e1"), square("e8")));
+    assert!(board.can_attack(square("e1"), square("a5")));
+}
+
+#[test]
+fn fen_errors() {
+    assert_eq!(Board::from_fen("8/8/8"), Err(FenError::WrongRankCount(3)));
+    assert_eq!(Board::from_fen(""), Err(FenError::WrongRankCount(1)));
+    assert_eq!(
+        Board::from_fen("8/8/8/8/8/8/8/7"),
+        Err(FenError::WrongRankLength(1))
+    );
+    assert_eq!(
+        Board::from_fen("9/8/8/8/8/8/8/8"),
+        Err(FenError::InvalidPiece('9'))
+    );
+    assert_eq!(
+        Board::from_fen("8/8/8/8/8/8/8/ppppppppp"),
+        Err(FenError::WrongRankLength(1))
+    );
+    assert_eq!(
+        Board::from_fen("8/8/8/8/8/8/8/x7"),
+        Err(FenError::InvalidPiece('x'))
+    );
+}
+
+#[test]
+fn n_queens_counts() {
+    assert_eq!(n_queens::<1>().count(), 1);
+    assert_eq!(n_queens::<2>().count(), 0);
+    assert_eq!(n_queens::<3>().count(), 0);
+    assert_eq!(n_queens::<4>().count(), 2);
+    assert_eq!(n_queens::<6>().count(), 4);
+    assert_eq!(n_queens::<8>().count(), 92);
+}
+
+#[test]
+fn n_queens_solutions_are_peaceful() {
+    for solution in n_queens::<8>() {
+        assert_eq!(solution.len(), 8);
+        let queens: Vec<Queen> = solution.into_iter().map(Queen::new).collect();
+        for (i, a) in queens.iter().enumerate() {
+            for b in &queens[i + 1..] {
+                assert!(!a.can_attack(b));
+            }
+        }
+    }
+}
+
+#[test]
+fn n_queens_first_solution_on_four() {
+    let solution: Vec<_> = n_queens::<4>().next().unwrap();
+    let expected: Vec<_> = [(0, 1), (1, 3), (2, 0), (3, 2)]
+        .into_iter()
+        .map(|(rank, file)| ChessPositionN::<4>::new(rank, file).unwrap())
+        .collect();
+    assert_eq!(solution, expected);
+}
+
+#[test]
+fn coverage_counts_attackers() {
+    assert_eq!(Board::new().coverage(), [[0; 8]; 8]);
+
+    let mut board = Board::new();
+    board.place(square("d4"), Color::White, Piece::Queen).unwrap();
+    let coverage = board.coverage();
+    assert_eq!(coverage.iter().flatten().map(|&n| n as u32).sum::<u32>(), 27);
+    assert_eq!(coverage[3][3], 0);
+    assert_eq!(coverage[7][7], 1);
+
+    board.place(square("d6"), Color::Black, Piece::Rook).unwrap();
+    let coverage = board.coverage();
+    // Both see d5; the rook hides d7 and d8 from the queen.
+    assert_eq!(coverage[4][3], 2);
+    assert_eq!(coverage[6][3], 1);
+    assert_eq!(coverage[5][3], 1);
+    assert_eq!(coverage[3][3], 1);
+}
+
+#[test]
+fn safe_squares_around_queens() {
+    assert_eq!(safe_squares(&[]).len(), 64);
+
+    let corner = Queen::new(square("a1"));
+    let safe = safe_squares(&[corner]);
+    assert_eq!(safe.len(), 64 - 1 - 21);
+    assert!(safe.contains(&square("b3")));
+    assert!(!safe.contains(&square("h8")));
+
+    let queens = [corner, Queen::new(square("c3"))];
+    let safe = safe_squares(&queens);
+    assert!(!safe.contains(&square("c3")));
+    assert!(!safe.contains(&square("h8")));
+    assert!(!safe.contains(&square("b4")));
+    assert!(safe.contains(&square("d8")));
+}
+
+#[test]
+fn no_safe_squares_left() {
+    let queens: Vec<Queen> = ["a1", "b1", "c1", "d1", "e1", "f1", "g1", "h1"]
+        .into_iter()
+        .map(|name| Queen::new(square(name)))
+        .collect();
+    assert_eq!(safe_squares(&queens), Vec::new());
+}
+
+#[test]
+fn attacking_pairs_of_queens() {
+    let queens: Vec<Queen> = ["a1", "h8", "a8", "c5", "d4"]
+        .into_iter()
+        .map(|name| Queen::new(square(name)))
+        .collect();
+    assert_eq!(
+        attacking_pairs(&queens),
+        vec![(0, 1), (0, 2), (0, 4), (1, 2), (1, 4), (3, 4)]
+    );
+    for (i, a) in queens.iter().enumerate() {
+        for (j, b) in queens.iter().enumerate().skip(i + 1) {
+            assert_eq!(a.can_attack(b), attacking_pairs(&queens).contains(&(i, j)));
+        }
+    }
+}
+
+#[test]
+fn attacking_pairs_score_n_queens() {
+    assert!(n_queens::<6>().all(|solution| {
+        let queens: Vec<_> = solution.into_iter().map(QueenN::new).collect();
+        attacking_pairs(&queens).is_empty()
+    }));
+    assert_eq!(attacking_pairs::<8>(&[]), Vec::new());
+    let same = Queen::new(square("e4"));
+    assert_eq!(attacking_pairs(&[same, same]), Vec::new());
+}
+
+#[test]
+fn queen_moves_capture_enemies_not_friends() {
+    let mut board = Board::new();
+    board.place(square("d4"), Color::White, Piece::Queen).unwrap();
+    board.place(square("d6"), Color::White, Piece::Pawn { color: Color::White }).unwrap();
+    board.place(square("f4"), Color::Black, Piece::Knight).unwrap();
+    let queen = Queen::new(square("d4"));
+
+    let moves: Vec<_> = queen.moves(&board).collect();
+    assert!(moves.contains(&square("d5")));
+    assert!(!moves.contains(&square("d6")));
+    assert!(!moves.contains(&square("d7")));
+    assert!(moves.contains(&square("e4")));
+    assert!(moves.contains(&square("f4")));
+    assert!(!moves.contains(&square("g4")));
+    assert_eq!(moves.len(), 27 - 3 - 2);
+}
+
+#[test]
+fn queen_off_the_board_treats_everything_as_an_enemy() {
+    let mut board = Board::new();
+    board.place(square("a3"), Color::White, Piece::Rook).unwrap();
+    let moves: Vec<_> = Queen::new(square("a1")).moves(&board).collect();
+    assert!(moves.contains(&square("a3")));
+    assert!(!moves.contains(&square("a4")));
+}
+
+#[test]
+fn render_the_starting_position() {
+    let board = Board::from_fen(START).unwrap();
+    let expected = "\
+8 ♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜
+7 ♟ ♟ ♟ ♟ ♟ ♟ ♟ ♟
+6 · · · · · · · ·
+5 · · · · · · · ·
+4 · · · · · · · ·
+3 · · · · · · · ·
+2 ♙ ♙ ♙ ♙ ♙ ♙ ♙ ♙
+1 ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖
+  a b c d e f g h";
+    assert_eq!(board.render(), expected);
+    assert_eq!(board.to_string(), expected);
+}
+
+#[test]
+fn render_marks_attacked_squares() {
+    let mut board = Board::new();
+    board.place(square("b2"), Color::White, Piece::Queen).unwrap();
+    board.place(square("b4"), Color::Black, Piece::Pawn { color: Color::Black }).unwrap();
+    let queen = Queen::new(square("b2"));
+    let expected = "\
+8 · · · · · · · ×
+7 · · · · · · × ·
+6 · · · · · × · ·
+5 · · · · × · · ·
+4 · ♟ · × · · · ·
+3 × × × · · · · ·
+2 × ♕ × × × × × ×
+1 × × × · · · · ·
+  a b c d e f g h";
+    assert_eq!(board.render_marked(queen.attacked_squares_on(&board)), expected);
+}
+
+#[test]
+fn bitboard_matches_the_geometry() {
+    // Off the standard board, pieces still step through the deltas; inside
+    // the first 8×8 squares of a 9×9 board the answers have to agree.
+    let pieces = [
+        Piece::Queen,
+        Piece::Rook,
+        Piece::Bishop,
+        Piece::Knight,
+        Piece::King,
+        Piece::Pawn { color: Color::White },
+        Piece::Pawn { color: Color::Black },
+    ];
+    for piece in pieces {
+        for from in 0..64 {
+            for to in 0..64 {
+                let standard = piece.can_attack(
+                    ChessPosition::new(from / 8, from % 8).unwrap(),
+                    ChessPosition::new(to / 8, to % 8).unwrap(),
+                );
+                let stepped = piece.can_attack(
+                    ChessPositionN::<9>::new(from / 8, from % 8).unwrap(),
+                    ChessPositionN::<9>::new(to / 8, to % 8).unwrap(),
+                );
+                assert_eq!(standard, stepped, "{piece:?} from {from} to {to}");
+            }
+        }
+    }
+}
+
+#[test]
+fn bitboard_sets() {
+    let mut set: Bitboard = [square("a1"), square("h8")].into_iter().collect();
+    assert_eq!(set, Bitboard(1 | 1 << 63));
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(square("h8")));
+    set.remove(square("h8"));
+    set.insert(square("c2"));
+    assert_eq!(set.squares().collect::<Vec<_>>(), vec![square("a1"), square("c2")]);
+    assert!(Bitboard::EMPTY.is_empty());
+}
+
+#[test]
+fn board_attacks_from() {
+    let board = Board::from_fen("8/8/8/3p4/8/8/8/R2Q4 w - - 0 1").unwrap();
+    assert_eq!(board.occupied().len(), 3);
+    let rook = board.attacks_from(square("a1"));
+    assert_eq!(rook.len(), 7 + 3);
+    assert!(rook.contains(square("d1")) && !rook.contains(square("e1")));
+    let queen = board.attacks_from(square("d1"));
+    assert!(queen.contains(square("d5")) && !queen.contains(square("d6")));
+    assert_eq!(queen, Queen::new(square("d1")).attacked_squares_on(&board).collect());
+    assert_eq!(board.attacks_from(square("e4")), Bitboard::EMPTY);
+    assert_eq!(
+        Piece::Knight.attacks_from(square("a1"), Bitboard::EMPTY),
+        [square("b3"), square("c2")].into_iter().collect()
+    );
+}
+
+#[test]
+fn distances() {
+    let (a1, c2, h8) = (square("a1"), square("c2"), square("h8"));
+    assert_eq!(a1.chebyshev_distance(&h8), 7);
+    assert_eq!(a1.chebyshev_distance(&c2), 2);
+    assert_eq!(a1.manhattan_distance(&h8), 14);
+    assert_eq!(c2.manhattan_distance(&a1), 3);
+    assert_eq!(a1.chebyshev_distance(&a1), 0);
+
+    assert_eq!(a1.queen_move_distance(&a1), 0);
+    assert_eq!(a1.queen_move_distance(&h8), 1);
+    assert_eq!(a1.queen_move_distance(&c2), 2);
+
+    let far = ChessPositionN::<10>::new(9, 8).unwrap();
+    let origin = ChessPositionN::<10>::new(0, 0).unwrap();
+    assert_eq!(origin.chebyshev_distance(&far), 9);
+    assert_eq!(origin.queen_move_distance(&far), 2);
+}
+
+#[test]
+fn positions_as_keys() {
+    use std::collections::{BTreeSet, HashMap};
+
+    let e4 = square("e4");
+    assert_eq!((e4.rank(), e4.file()), (3, 4));
+
+    let sorted: BTreeSet<_> = ["h1", "a2", "b1", "a1"].into_iter().map(square).collect();
+    assert_eq!(
+        sorted.into_iter().collect::<Vec<_>>(),
+        vec![square("a1"), square("b1"), square("h1"), square("a2")]
+    );
+
+    let mut visits = HashMap::new();
+    *visits.entry(e4).or_insert(0) += 1;
+    *visits.entry(square("e4")).or_insert(0) += 1;
+    assert_eq!(visits[&e4], 2);
+}
+
+#[test]
+fn shortest_route_on_an_empty_board() {
+    let board = Board::new();
+    assert_eq!(shortest_route(square("a1"), square("a1"), &board), Some(vec![square("a1")]));
+    assert_eq!(
+        shortest_route(square("a1"), square("h8"), &board),
+        Some(vec![square("a1"), square("h8")])
+    );
+    let route = shortest_route(square("a1"), square("b3"), &board).unwrap();
+    assert_eq!(route.len(), 3);
+}
+
+#[test]
+fn shortest_route_around_blockers() {
+    // A wall across the third rank with one gap, on h3.
+    let board = Board::from_fen("8/8/8/8/8/PPPPPPP1/8/8 w - - 0 1").unwrap();
+    let route = shortest_route(square("a1"), square("a8"), &board).unwrap();
+    assert_eq!(route.first(), Some(&square("a1")));
+    assert_eq!(route.last(), Some(&square("a8")));
+    for step in route.windows(2) {
+        let path = Queen::new(step[0]).attack_path(&Queen::new(step[1])).unwrap();
+        assert!(path.iter().chain(&step[1..]).all(|&square| board.get(square).is_none()));
+    }
+    // Over to the h-file, up through the gap, and back along the eighth.
+    assert_eq!(route.len(), 4);
+}
+
+#[test]
+fn no_route_when_walled_off() {
+    let board = Board::from_fen("8/8/8/8/8/PPPPPPPP/8/8 w - - 0 1").unwrap();
+    assert_eq!(shortest_route(square("a1"), square("a8"), &board), None);
+    assert_eq!(shortest_route(square("a1"), square("a3"), &board), None);
+}